@@ -0,0 +1,27 @@
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(portable_simd)");
+    if std::env::var_os("CARGO_FEATURE_PORTABLE_SIMD").is_some() {
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let version = std::process::Command::new(rustc)
+            .arg("--version")
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default();
+        if version.contains("nightly") {
+            println!("cargo:rustc-cfg=portable_simd");
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(config)
+            .generate()
+            .expect("unable to generate C bindings")
+            .write_to_file(format!("{}/psboxs.h", std::env::var("OUT_DIR").unwrap()));
+    }
+}
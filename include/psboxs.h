@@ -0,0 +1,140 @@
+#ifndef PSBOXS_H
+#define PSBOXS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct PBox PBox;
+
+typedef struct SBox SBox;
+
+typedef struct Spn Spn;
+
+/**
+ * # Safety
+ * `table` must point to `len` readable values.
+ */
+struct SBox *psboxs_sbox_new(const uint32_t *table, size_t len);
+
+/**
+ * # Safety
+ * `s_box` must come from `psboxs_sbox_new` and must not be used afterwards.
+ */
+void psboxs_sbox_free(struct SBox *s_box);
+
+/**
+ * # Safety
+ * `s_box` must be a live pointer from `psboxs_sbox_new`.
+ */
+size_t psboxs_sbox_input_bits(const struct SBox *s_box);
+
+/**
+ * # Safety
+ * `s_box` must be a live pointer from `psboxs_sbox_new`.
+ */
+size_t psboxs_sbox_output_bits(const struct SBox *s_box);
+
+/**
+ * # Safety
+ * `s_box` must be live; `input` and `output` must each hold `len` values.
+ */
+void psboxs_sbox_encrypt(const struct SBox *s_box,
+                         const uint32_t *input,
+                         uint32_t *output,
+                         size_t len);
+
+/**
+ * # Safety
+ * `s_box` must be live; `input` and `output` must each hold `len` values.
+ */
+void psboxs_sbox_decrypt(const struct SBox *s_box,
+                         const uint32_t *input,
+                         uint32_t *output,
+                         size_t len);
+
+/**
+ * Copies up to `out_len` table entries into `out` and returns the table size.
+ *
+ * # Safety
+ * `s_box` must be live; `out` must be null or hold `out_len` values.
+ */
+size_t psboxs_sbox_table(const struct SBox *s_box, uint32_t *out, size_t out_len);
+
+/**
+ * Copies up to `out_len` entries of the row-major DDT into `out` and returns its size.
+ *
+ * # Safety
+ * `s_box` must be live; `out` must be null or hold `out_len` values.
+ */
+size_t psboxs_sbox_ddt(const struct SBox *s_box, uint32_t *out, size_t out_len);
+
+/**
+ * Copies up to `out_len` entries of the row-major LAT into `out` and returns its size.
+ *
+ * # Safety
+ * `s_box` must be live; `out` must be null or hold `out_len` values.
+ */
+size_t psboxs_sbox_lat(const struct SBox *s_box, int32_t *out, size_t out_len);
+
+/**
+ * Returns null for an invalid permutation or one wider than 32 bits.
+ *
+ * # Safety
+ * `permutation` must point to `len` readable values.
+ */
+struct PBox *psboxs_pbox_new(const uint32_t *permutation, size_t len);
+
+/**
+ * # Safety
+ * `p_box` must come from `psboxs_pbox_new` and must not be used afterwards.
+ */
+void psboxs_pbox_free(struct PBox *p_box);
+
+/**
+ * # Safety
+ * `p_box` must be live; `input` and `output` must each hold `len` values.
+ */
+void psboxs_pbox_encrypt(const struct PBox *p_box,
+                         const uint32_t *input,
+                         uint32_t *output,
+                         size_t len);
+
+/**
+ * # Safety
+ * `p_box` must be live; `input` and `output` must each hold `len` values.
+ */
+void psboxs_pbox_decrypt(const struct PBox *p_box,
+                         const uint32_t *input,
+                         uint32_t *output,
+                         size_t len);
+
+/**
+ * Builds an SPN from copies of the given boxes; returns null on invalid input.
+ *
+ * # Safety
+ * `s_box` and `p_box` must be live; `round_keys` must hold `round_key_count` values.
+ */
+struct Spn *psboxs_spn_new(const struct SBox *s_box,
+                           const struct PBox *p_box,
+                           const uint32_t *round_keys,
+                           size_t round_key_count);
+
+/**
+ * # Safety
+ * `spn` must come from `psboxs_spn_new` and must not be used afterwards.
+ */
+void psboxs_spn_free(struct Spn *spn);
+
+/**
+ * # Safety
+ * `spn` must be live; `input` and `output` must each hold `len` values.
+ */
+void psboxs_spn_encrypt(const struct Spn *spn, const uint32_t *input, uint32_t *output, size_t len);
+
+/**
+ * # Safety
+ * `spn` must be live; `input` and `output` must each hold `len` values.
+ */
+void psboxs_spn_decrypt(const struct Spn *spn, const uint32_t *input, uint32_t *output, size_t len);
+
+#endif  /* PSBOXS_H */
@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+
+use crate::SBox;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+impl SBox {
+    pub fn ddt_row(&self, input_difference: u32) -> Vec<u32> {
+        let mut row = vec![0; 1 << self.output_bits];
+        for (x, &y) in self.table.iter().enumerate() {
+            let other = self.table[x ^ input_difference as usize];
+            row[(y ^ other) as usize] += 1;
+        }
+
+        row
+    }
+
+    pub fn ddt_rows(&self) -> impl Iterator<Item = Vec<u32>> + '_ {
+        (0..1u32 << self.input_bits).map(|a| self.ddt_row(a))
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    pub fn ddt(&self) -> Vec<Vec<u32>> {
+        self.ddt_rows().collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn ddt(&self) -> Vec<Vec<u32>> {
+        (0..1u32 << self.input_bits)
+            .into_par_iter()
+            .map(|a| self.ddt_row(a))
+            .collect()
+    }
+
+    pub fn lat_row(&self, input_mask: u32) -> Vec<i32> {
+        let mut row = vec![0i32; 1 << self.output_bits];
+        for (x, &y) in self.table.iter().enumerate() {
+            if (x as u32 & input_mask).count_ones() & 1 == 0 {
+                row[y as usize] += 1;
+            } else {
+                row[y as usize] -= 1;
+            }
+        }
+
+        walsh_hadamard(&mut row);
+        for el in row.iter_mut() {
+            *el /= 2;
+        }
+
+        row
+    }
+
+    pub fn lat_rows(&self) -> impl Iterator<Item = Vec<i32>> + '_ {
+        (0..1u32 << self.input_bits).map(|a| self.lat_row(a))
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    pub fn lat(&self) -> Vec<Vec<i32>> {
+        self.lat_rows().collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn lat(&self) -> Vec<Vec<i32>> {
+        (0..1u32 << self.input_bits)
+            .into_par_iter()
+            .map(|a| self.lat_row(a))
+            .collect()
+    }
+
+    pub fn bct_row(&self, input_difference: u32) -> Vec<u32> {
+        let inverse_table = self.inverse_table();
+
+        let mut row = vec![0; 1 << self.output_bits];
+        for (b, el) in row.iter_mut().enumerate() {
+            for (x, &y) in self.table.iter().enumerate() {
+                let other = self.table[x ^ input_difference as usize];
+                let a = inverse_table[(y ^ b as u32) as usize]
+                    ^ inverse_table[(other ^ b as u32) as usize];
+                *el += (a == input_difference) as u32;
+            }
+        }
+
+        row
+    }
+
+    pub fn bct_rows(&self) -> impl Iterator<Item = Vec<u32>> + '_ {
+        (0..1u32 << self.input_bits).map(|a| self.bct_row(a))
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    pub fn bct(&self) -> Vec<Vec<u32>> {
+        self.bct_rows().collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn bct(&self) -> Vec<Vec<u32>> {
+        (0..1u32 << self.input_bits)
+            .into_par_iter()
+            .map(|a| self.bct_row(a))
+            .collect()
+    }
+
+    pub fn differential_uniformity(&self) -> u32 {
+        self.ddt_rows()
+            .skip(1)
+            .flat_map(|row| row.into_iter())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn differential_spectrum(&self) -> BTreeMap<u32, usize> {
+        let mut spectrum = BTreeMap::new();
+        for el in self.ddt_rows().skip(1).flatten() {
+            *spectrum.entry(el).or_insert(0) += 1;
+        }
+
+        spectrum
+    }
+
+    pub fn differential_branch_number(&self) -> u32 {
+        self.ddt_rows()
+            .enumerate()
+            .skip(1)
+            .flat_map(|(a, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .filter(|&(_, el)| el != 0)
+                    .map(move |(b, _)| a.count_ones() + b.count_ones())
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub fn linear_branch_number(&self) -> u32 {
+        self.lat_rows()
+            .enumerate()
+            .flat_map(|(a, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .skip(1)
+                    .filter(|&(_, el)| el != 0)
+                    .map(move |(b, _)| a.count_ones() + b.count_ones())
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub fn linearity(&self) -> u32 {
+        self.lat_rows()
+            .flat_map(|row| row.into_iter().skip(1))
+            .map(|el| el.unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn nonlinearity(&self) -> u32 {
+        (1u32 << self.input_bits) / 2 - self.linearity()
+    }
+}
+
+pub(crate) fn walsh_hadamard(values: &mut [i32]) {
+    let mut len = 1;
+    while len < values.len() {
+        for i in (0..values.len()).step_by(len << 1) {
+            for j in i..i + len {
+                let (a, b) = (values[j], values[j + len]);
+                values[j] = a + b;
+                values[j + len] = a - b;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_s_box, present_s_box};
+
+    #[test]
+    fn test_ddt() {
+        let ddt = heys_s_box().ddt();
+        assert_eq!(ddt[0][0], 16);
+        assert_eq!(ddt[0xb][0x2], 8);
+        assert_eq!(ddt[0x4][0x6], 6);
+        for row in &ddt {
+            assert_eq!(row.iter().sum::<u32>(), 16);
+        }
+    }
+
+    #[test]
+    fn test_lat() {
+        let lat = heys_s_box().lat();
+        assert_eq!(lat[0][0], 8);
+        assert_eq!(lat[0xb][0x4], 4);
+        assert_eq!(lat[0x4][0x5], -4);
+        assert_eq!(lat[0x6][0xb], 4);
+        assert_eq!(lat[0x9][0x4], 0);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let s_box = heys_s_box();
+        assert_eq!(s_box.differential_uniformity(), 8);
+        assert_eq!(s_box.linearity(), 6);
+        assert_eq!(s_box.nonlinearity(), 2);
+
+        let identity = SBox::from_flat((0..16).collect(), 4, 4).unwrap();
+        assert_eq!(identity.differential_uniformity(), 16);
+        assert_eq!(identity.linearity(), 8);
+        assert_eq!(identity.nonlinearity(), 0);
+        assert_eq!(identity.differential_branch_number(), 2);
+        assert_eq!(identity.linear_branch_number(), 2);
+
+        let present = present_s_box();
+        assert_eq!(present.differential_branch_number(), 3);
+        assert_eq!(present.linear_branch_number(), 2);
+    }
+
+    #[test]
+    fn test_differential_spectrum() {
+        let spectrum = heys_s_box().differential_spectrum();
+        assert_eq!(spectrum.values().sum::<usize>(), 15 * 16);
+        assert_eq!(spectrum.keys().last(), Some(&8));
+        assert_eq!(
+            spectrum
+                .iter()
+                .map(|(&v, &c)| v as usize * c)
+                .sum::<usize>(),
+            15 * 16
+        );
+
+        let present = present_s_box();
+        assert_eq!(
+            present.differential_spectrum(),
+            BTreeMap::from([(0, 144), (2, 72), (4, 24)])
+        );
+    }
+
+    #[test]
+    fn test_bct() {
+        let s_box = heys_s_box();
+        let ddt = s_box.ddt();
+        let bct = s_box.bct();
+        for a in 0..16 {
+            assert_eq!(bct[0][a], 16);
+            assert_eq!(bct[a][0], 16);
+            for b in 0..16 {
+                assert!(bct[a][b] >= ddt[a][b]);
+            }
+        }
+    }
+}
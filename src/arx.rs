@@ -0,0 +1,201 @@
+use crate::{bits2num, num2bits};
+
+pub struct AddMod2n {
+    width: usize,
+    key: u32,
+}
+
+impl AddMod2n {
+    pub fn new(width: usize, key: u32) -> Result<AddMod2n, &'static str> {
+        if width == 0 || width > 32 {
+            return Err("invalid width");
+        }
+        if width < 32 && key >> width != 0 {
+            return Err("key does not fit in width");
+        }
+
+        Ok(AddMod2n { width, key })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn key(&self) -> u32 {
+        self.key
+    }
+
+    fn mask(&self) -> u32 {
+        (u64::MAX >> (64 - self.width)) as u32
+    }
+
+    pub fn encrypt_value(&self, value: u32) -> u32 {
+        value.wrapping_add(self.key) & self.mask()
+    }
+
+    pub fn decrypt_value(&self, value: u32) -> u32 {
+        value.wrapping_sub(self.key) & self.mask()
+    }
+
+    fn transform(&self, bits: &[bool], f: impl Fn(u32) -> u32) -> Vec<bool> {
+        assert!(bits.len().is_multiple_of(self.width));
+
+        bits.chunks(self.width)
+            .flat_map(|lane| num2bits(f(bits2num(lane)), self.width))
+            .collect()
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        self.transform(bits, |value| self.encrypt_value(value))
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
+        self.transform(bits, |value| self.decrypt_value(value))
+    }
+}
+
+pub struct RxLayer {
+    lane_width: usize,
+    lanes: Vec<Vec<(usize, u32)>>,
+    inverse_columns: Option<Vec<u32>>,
+}
+
+impl RxLayer {
+    pub fn new(lane_width: usize, lanes: Vec<Vec<(usize, u32)>>) -> Result<RxLayer, &'static str> {
+        if lane_width == 0 || lanes.is_empty() || lane_width * lanes.len() > 32 {
+            return Err("invalid width");
+        }
+        if lanes
+            .iter()
+            .flatten()
+            .any(|&(source, _)| source >= lanes.len())
+        {
+            return Err("invalid source lane");
+        }
+
+        let mut layer = RxLayer {
+            lane_width,
+            lanes,
+            inverse_columns: None,
+        };
+        layer.inverse_columns = layer.invert();
+
+        Ok(layer)
+    }
+
+    pub fn width(&self) -> usize {
+        self.lane_width * self.lanes.len()
+    }
+
+    pub fn is_invertible(&self) -> bool {
+        self.inverse_columns.is_some()
+    }
+
+    fn rotate(&self, lane: u32, rotation: u32) -> u32 {
+        let width = self.lane_width as u32;
+        let mask = (u64::MAX >> (64 - width)) as u32;
+        let rotation = rotation % width;
+        if rotation == 0 {
+            return lane;
+        }
+
+        ((lane << rotation) | (lane >> (width - rotation))) & mask
+    }
+
+    fn lane(&self, value: u32, index: usize) -> u32 {
+        let shift = self.width() - (index + 1) * self.lane_width;
+        ((value as u64 >> shift) & (u64::MAX >> (64 - self.lane_width))) as u32
+    }
+
+    pub fn encrypt_value(&self, value: u32) -> u32 {
+        self.lanes.iter().fold(0u64, |acc, terms| {
+            let lane = terms.iter().fold(0, |lane, &(source, rotation)| {
+                lane ^ self.rotate(self.lane(value, source), rotation)
+            });
+            (acc << self.lane_width) | lane as u64
+        }) as u32
+    }
+
+    fn invert(&self) -> Option<Vec<u32>> {
+        let n = self.width();
+        let mut rows: Vec<(u32, u32)> = (0..n)
+            .map(|k| (self.encrypt_value(1 << k), 1 << k))
+            .collect();
+
+        for bit in 0..n {
+            let pivot = (bit..n).find(|&i| (rows[i].0 >> bit) & 1 == 1)?;
+            rows.swap(bit, pivot);
+            let (column, inverse) = rows[bit];
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != bit && (row.0 >> bit) & 1 == 1 {
+                    row.0 ^= column;
+                    row.1 ^= inverse;
+                }
+            }
+        }
+
+        Some(rows.into_iter().map(|(_, inverse)| inverse).collect())
+    }
+
+    pub fn decrypt_value(&self, value: u32) -> Result<u32, &'static str> {
+        let columns = self
+            .inverse_columns
+            .as_ref()
+            .ok_or("layer is not invertible")?;
+
+        Ok(columns
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| (value >> k) & 1 == 1)
+            .fold(0, |acc, (_, &column)| acc ^ column))
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(self.encrypt_value(bits2num(bits)), self.width())
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Result<Vec<bool>, &'static str> {
+        Ok(num2bits(self.decrypt_value(bits2num(bits))?, self.width()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_mod_2n() {
+        let layer = AddMod2n::new(8, 0xf0).unwrap();
+        assert_eq!(layer.encrypt_value(0x20), 0x10);
+        assert_eq!(layer.decrypt_value(0x10), 0x20);
+
+        let bits = num2bits(0x20ff, 16);
+        let encrypted = layer.encrypt(&bits);
+        assert_eq!(bits2num(&encrypted), 0x10ef);
+        assert_eq!(layer.decrypt(&encrypted), bits);
+
+        let full = AddMod2n::new(32, u32::MAX).unwrap();
+        assert_eq!(full.encrypt_value(1), 0);
+        assert!(AddMod2n::new(4, 0x10).is_err());
+        assert!(AddMod2n::new(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_rx_layer() {
+        let layer = RxLayer::new(16, vec![vec![(0, 0), (0, 2), (0, 7)]]).unwrap();
+        assert_eq!(layer.encrypt_value(0x0001), 0x0085);
+        for x in [0x0000, 0x26b7, 0xffff, 0x8001] {
+            let bits = num2bits(x, 16);
+            assert_eq!(layer.decrypt(&layer.encrypt(&bits)).unwrap(), bits);
+        }
+
+        let lanes = RxLayer::new(8, vec![vec![(0, 0), (1, 3)], vec![(1, 0)]]).unwrap();
+        assert_eq!(lanes.encrypt_value(0x0121), 0x0821);
+        assert_eq!(lanes.decrypt_value(0x0821).unwrap(), 0x0121);
+
+        let singular = RxLayer::new(16, vec![vec![(0, 0), (0, 1)]]).unwrap();
+        assert!(!singular.is_invertible());
+        assert!(singular.decrypt_value(0).is_err());
+        assert!(RxLayer::new(8, vec![vec![(1, 0)]]).is_err());
+    }
+}
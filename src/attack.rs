@@ -0,0 +1,189 @@
+use crate::{DifferentialTrail, LinearTrail, Spn};
+
+pub struct KeyRecovery {
+    pub mask: u32,
+    pub key: u32,
+    pub count: usize,
+}
+
+impl Spn {
+    fn last_round_mask(&self, difference: u32) -> u32 {
+        let s_box_mask = (1 << self.s_box().input_bits()) - 1;
+        let active = (0..self.s_box_count())
+            .filter(|&j| self.chunk(difference, j) != 0)
+            .fold(0, |acc, j| self.with_chunk(acc, j, s_box_mask));
+
+        self.p_box().encrypt_value(active)
+    }
+
+    fn key_candidates(&self, mask: u32) -> Vec<u32> {
+        let positions: Vec<u32> = (0..32).filter(|&i| (mask >> i) & 1 == 1).collect();
+
+        (0..1u32 << positions.len())
+            .map(|x| {
+                positions
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, &position)| acc | ((x >> i) & 1) << position)
+            })
+            .collect()
+    }
+
+    fn partial_decrypt(&self, ciphertext: u32, key: u32) -> u32 {
+        let state = self.p_box().decrypt_value(ciphertext ^ key);
+        let inverse_table = self.s_box().inverse_table();
+
+        (0..self.s_box_count()).fold(0, |acc, j| {
+            self.with_chunk(acc, j, inverse_table[self.chunk(state, j) as usize])
+        })
+    }
+
+    pub fn differential_key_recovery(
+        &self,
+        trail: &DifferentialTrail,
+        pairs: usize,
+        mut random: impl FnMut() -> u32,
+        mut oracle: impl FnMut(u32) -> u32,
+    ) -> Result<KeyRecovery, &'static str> {
+        self.check_word_size()?;
+        if trail.differences.len() != self.rounds() {
+            return Err("trail must cover all rounds but the last");
+        }
+
+        let n = self.block_size();
+        let input = trail.differences[0];
+        let output = trail.differences[self.rounds() - 1];
+        let mask = self.last_round_mask(output);
+
+        let mut ciphertexts = Vec::new();
+        for _ in 0..pairs {
+            let x = random() & (u64::MAX >> (64 - n)) as u32;
+            let (a, b) = (oracle(x), oracle(x ^ input));
+            if (a ^ b) & !mask == 0 {
+                ciphertexts.push((a, b));
+            }
+        }
+
+        let mut best = KeyRecovery {
+            mask,
+            key: 0,
+            count: 0,
+        };
+        for key in self.key_candidates(mask) {
+            let count = ciphertexts
+                .iter()
+                .filter(|&&(a, b)| {
+                    self.partial_decrypt(a, key) ^ self.partial_decrypt(b, key) == output
+                })
+                .count();
+            if count > best.count {
+                best = KeyRecovery { mask, key, count };
+            }
+        }
+
+        Ok(best)
+    }
+
+    pub fn linear_key_recovery(
+        &self,
+        trail: &LinearTrail,
+        samples: usize,
+        mut random: impl FnMut() -> u32,
+        mut oracle: impl FnMut(u32) -> u32,
+    ) -> Result<KeyRecovery, &'static str> {
+        self.check_word_size()?;
+        if trail.masks.len() != self.rounds() {
+            return Err("trail must cover all rounds but the last");
+        }
+
+        let n = self.block_size();
+        let input = trail.masks[0];
+        let output = trail.masks[self.rounds() - 1];
+        let mask = self.last_round_mask(output);
+
+        let texts: Vec<(u32, u32)> = (0..samples)
+            .map(|_| {
+                let x = random() & (u64::MAX >> (64 - n)) as u32;
+                (x, oracle(x))
+            })
+            .collect();
+
+        let mut best = KeyRecovery {
+            mask,
+            key: 0,
+            count: 0,
+        };
+        for key in self.key_candidates(mask) {
+            let matches = texts
+                .iter()
+                .filter(|&&(x, y)| {
+                    let u = self.partial_decrypt(y, key);
+                    (x & input).count_ones() % 2 == (u & output).count_ones() % 2
+                })
+                .count();
+            let count = matches.abs_diff(samples / 2);
+            if count > best.count {
+                best = KeyRecovery { mask, key, count };
+            }
+        }
+
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::heys_spn;
+    use crate::{bits2num, num2bits, LinearTrail, Spn};
+
+    fn random() -> impl FnMut() -> u32 {
+        let mut seed = 0x1234u32;
+        move || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 8
+        }
+    }
+
+    #[test]
+    fn test_differential_key_recovery() {
+        let spn = heys_spn();
+        let trail = spn.best_differential_trail(3).unwrap();
+        let oracle = |x| bits2num(&spn.encrypt(&num2bits(x, 16)));
+
+        let recovery = spn
+            .differential_key_recovery(&trail, 5000, random(), oracle)
+            .unwrap();
+        assert_eq!(recovery.key, 0xd63f & recovery.mask);
+
+        let short = spn.best_differential_trail(2).unwrap();
+        assert!(spn
+            .differential_key_recovery(&short, 1, random(), oracle)
+            .is_err());
+    }
+
+    #[test]
+    fn test_linear_key_recovery() {
+        let spn = heys_spn();
+        let trail = LinearTrail {
+            masks: vec![0x0b00, 0x0400, 0x0404, 0x0505],
+            weight: 4.0,
+        };
+        let oracle = |x| bits2num(&spn.encrypt(&num2bits(x, 16)));
+
+        let recovery = spn
+            .linear_key_recovery(&trail, 10000, random(), oracle)
+            .unwrap();
+        assert_eq!(recovery.key, 0xd63f & recovery.mask);
+    }
+
+    #[test]
+    fn test_present_is_rejected() {
+        let spn = Spn::present80(0).unwrap();
+        let trail = LinearTrail {
+            masks: vec![1; spn.rounds()],
+            weight: 0.0,
+        };
+
+        assert!(spn.linear_key_recovery(&trail, 1, random(), |x| x).is_err());
+    }
+}
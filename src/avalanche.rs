@@ -0,0 +1,284 @@
+use crate::{bits2num, num2bits, BlockCipher, SBox, Spn};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+impl Spn {
+    fn encrypt_rounds(&self, bits: &[bool], rounds: usize) -> u32 {
+        let mut state = bits.to_vec();
+        for round in 0..rounds {
+            state = self.encrypt_round(&state, round);
+        }
+
+        bits2num(&state)
+    }
+
+    pub fn avalanche_matrix(
+        &self,
+        rounds: usize,
+        samples: usize,
+        mut random: impl FnMut() -> u32,
+    ) -> Result<Vec<Vec<f64>>, &'static str> {
+        self.check_word_size()?;
+        if samples == 0 {
+            return Err("no samples");
+        }
+
+        let n = self.block_size();
+        let mask = (u64::MAX >> (64 - n)) as u32;
+
+        let mut counts = vec![vec![0usize; n]; n];
+        for _ in 0..samples {
+            let x = random() & mask;
+            let y = self.encrypt_rounds(&num2bits(x, n), rounds);
+            for (i, row) in counts.iter_mut().enumerate() {
+                let flipped = x ^ (1 << (n - 1 - i));
+                let difference = y ^ self.encrypt_rounds(&num2bits(flipped, n), rounds);
+                for (j, count) in row.iter_mut().enumerate() {
+                    *count += ((difference >> (n - 1 - j)) & 1) as usize;
+                }
+            }
+        }
+
+        Ok(counts
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| c as f64 / samples as f64).collect())
+            .collect())
+    }
+
+    pub fn rounds_to_full_diffusion(
+        &self,
+        samples: usize,
+        mut random: impl FnMut() -> u32,
+    ) -> Option<usize> {
+        self.check_word_size().ok()?;
+
+        (1..=self.rounds()).find(|&rounds| {
+            self.avalanche_matrix(rounds, samples, &mut random)
+                .is_ok_and(|matrix| matrix.iter().flatten().all(|&p| p > 0.0))
+        })
+    }
+}
+
+impl Spn {
+    pub fn dependency_matrix(&self, rounds: usize) -> Vec<Vec<bool>> {
+        let n = self.block_size();
+        let s_box_size = self.s_box().input_bits();
+        let s_box_dependency: Vec<Vec<bool>> = self
+            .s_box()
+            .avalanche_matrix()
+            .iter()
+            .map(|row| row.iter().map(|&p| p > 0.0).collect())
+            .collect();
+
+        (0..n)
+            .map(|i| {
+                let mut state = vec![false; n];
+                state[i] = true;
+                for _ in 0..rounds {
+                    let substituted: Vec<bool> = state
+                        .chunks(s_box_size)
+                        .flat_map(|chunk| {
+                            (0..s_box_size).map(|j| {
+                                chunk
+                                    .iter()
+                                    .zip(&s_box_dependency)
+                                    .any(|(&dependent, row)| dependent && row[j])
+                            })
+                        })
+                        .collect();
+                    state = self.p_box().encrypt(&substituted);
+                }
+
+                state
+            })
+            .collect()
+    }
+
+    pub fn rounds_to_full_dependence(&self) -> Option<usize> {
+        (1..=self.rounds()).find(|&rounds| {
+            self.dependency_matrix(rounds)
+                .iter()
+                .flatten()
+                .all(|&dependent| dependent)
+        })
+    }
+}
+
+impl SBox {
+    pub fn avalanche_matrix(&self) -> Vec<Vec<f64>> {
+        let (n, m) = (self.input_bits, self.output_bits);
+        let size = self.table.len();
+
+        (0..n)
+            .map(|i| {
+                let flip = 1 << (n - 1 - i);
+                (0..m)
+                    .map(|j| {
+                        let bit = 1 << (m - 1 - j);
+                        let count = (0..size)
+                            .filter(|&x| (self.table[x] ^ self.table[x ^ flip]) & bit != 0)
+                            .count();
+                        count as f64 / size as f64
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn dependence_counts<C: BlockCipher>(
+    cipher: &impl Fn(&[bool]) -> C,
+    key: &[bool],
+    plaintext: &[bool],
+) -> Vec<Vec<usize>> {
+    let cipher = cipher(key);
+    let ciphertext = cipher.encrypt_block(plaintext);
+
+    (0..plaintext.len())
+        .map(|i| {
+            let mut flipped = plaintext.to_vec();
+            flipped[i] = !flipped[i];
+            cipher
+                .encrypt_block(&flipped)
+                .iter()
+                .zip(&ciphertext)
+                .map(|(a, b)| (a != b) as usize)
+                .collect()
+        })
+        .collect()
+}
+
+fn add_counts(mut a: Vec<Vec<usize>>, b: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    for (row, other) in a.iter_mut().zip(b) {
+        for (count, other) in row.iter_mut().zip(other) {
+            *count += other;
+        }
+    }
+
+    a
+}
+
+fn dependence_samples(
+    key_bits: usize,
+    block_size: usize,
+    samples: usize,
+    mut random: impl FnMut() -> u32,
+) -> Vec<(Vec<bool>, Vec<bool>)> {
+    let mut random_bits = |n: usize| (0..n).map(|_| random() & 1 == 1).collect::<Vec<bool>>();
+
+    (0..samples)
+        .map(|_| (random_bits(key_bits), random_bits(block_size)))
+        .collect()
+}
+
+fn dependence_probabilities(counts: Vec<Vec<usize>>, samples: usize) -> Vec<Vec<f64>> {
+    counts
+        .into_iter()
+        .map(|row| row.into_iter().map(|c| c as f64 / samples as f64).collect())
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn dependence_matrix<C: BlockCipher>(
+    cipher: impl Fn(&[bool]) -> C + Sync,
+    key_bits: usize,
+    samples: usize,
+    random: impl FnMut() -> u32,
+) -> Vec<Vec<f64>> {
+    let n = cipher(&vec![false; key_bits]).block_size();
+    let counts = dependence_samples(key_bits, n, samples, random)
+        .iter()
+        .map(|(key, plaintext)| dependence_counts(&cipher, key, plaintext))
+        .fold(vec![vec![0; n]; n], add_counts);
+
+    dependence_probabilities(counts, samples)
+}
+
+#[cfg(feature = "parallel")]
+pub fn dependence_matrix<C: BlockCipher>(
+    cipher: impl Fn(&[bool]) -> C + Sync,
+    key_bits: usize,
+    samples: usize,
+    random: impl FnMut() -> u32,
+) -> Vec<Vec<f64>> {
+    let n = cipher(&vec![false; key_bits]).block_size();
+    let counts = dependence_samples(key_bits, n, samples, random)
+        .par_iter()
+        .map(|(key, plaintext)| dependence_counts(&cipher, key, plaintext))
+        .reduce(|| vec![vec![0; n]; n], add_counts);
+
+    dependence_probabilities(counts, samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dependence_matrix;
+    use crate::fixtures::{heys_p_box, heys_s_box};
+    use crate::{num2bits, SBox, Spn};
+
+    #[test]
+    fn test_avalanche() {
+        let spn = Spn::new(heys_s_box(), heys_p_box(), vec![num2bits(0, 16); 5]).unwrap();
+
+        let mut seed = 0x1234u32;
+        let mut random = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 8
+        };
+
+        let matrix = spn.avalanche_matrix(1, 256, &mut random).unwrap();
+        assert_eq!(matrix.len(), 16);
+        assert!(matrix[0][0] > 0.0);
+        assert_eq!(matrix[0][1], 0.0);
+        assert_eq!(spn.rounds_to_full_diffusion(256, &mut random), Some(2));
+        assert_eq!(spn.rounds_to_full_dependence(), Some(2));
+
+        let present = Spn::present80(0).unwrap();
+        assert!(present.avalanche_matrix(1, 1, &mut random).is_err());
+        assert_eq!(spn.avalanche_matrix(1, 0, &mut random), Err("no samples"));
+        assert_eq!(spn.rounds_to_full_diffusion(0, &mut random), None);
+        assert_eq!(present.rounds_to_full_diffusion(1, &mut random), None);
+    }
+
+    #[test]
+    fn test_s_box_avalanche() {
+        let identity = SBox::from_flat((0..16).collect(), 4, 4).unwrap();
+        let matrix = identity.avalanche_matrix();
+        assert_eq!(matrix[1][1], 1.0);
+        assert_eq!(matrix[1][2], 0.0);
+
+        let s_box = SBox::from_flat(vec![2, 0, 3, 1], 2, 2).unwrap();
+        assert_eq!(
+            s_box.avalanche_matrix(),
+            vec![vec![0.0, 1.0], vec![1.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn test_dependency_matrix() {
+        let present = Spn::present80(0).unwrap();
+        let matrix = present.dependency_matrix(1);
+        assert_eq!(matrix[0].iter().filter(|&&d| d).count(), 4);
+        assert!(!present.dependency_matrix(2).iter().flatten().all(|&d| d));
+        assert_eq!(present.rounds_to_full_dependence(), Some(3));
+    }
+
+    #[test]
+    fn test_dependence_matrix() {
+        let heys = |key: &[bool]| {
+            let round_keys = key.chunks(16).map(|k| k.to_vec()).collect();
+            Spn::new(heys_s_box(), heys_p_box(), round_keys).unwrap()
+        };
+
+        let mut seed = 0x1234u32;
+        let random = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 8
+        };
+
+        let matrix = dependence_matrix(heys, 80, 512, random);
+        assert_eq!(matrix.len(), 16);
+        assert!(matrix.iter().flatten().all(|&p| p > 0.1 && p < 0.9));
+    }
+}
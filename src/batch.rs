@@ -0,0 +1,165 @@
+use crate::{PBox, SBox, Spn};
+
+fn value_bits(bits: &[bool]) -> u64 {
+    bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as u64)
+}
+
+fn check_blocks(blocks: &[u64], bit_count: usize) -> Result<(), &'static str> {
+    if bit_count < 64 && blocks.iter().any(|&block| block >> bit_count != 0) {
+        return Err("block does not fit the block size");
+    }
+
+    Ok(())
+}
+
+impl SBox {
+    pub fn encrypt_many(&self, blocks: &[u64]) -> Result<Vec<u64>, &'static str> {
+        check_blocks(blocks, self.input_bits)?;
+
+        Ok(blocks
+            .iter()
+            .map(|&x| self.table[x as usize] as u64)
+            .collect())
+    }
+
+    pub fn decrypt_many(&self, blocks: &[u64]) -> Result<Vec<u64>, &'static str> {
+        let inverse_table = self.inverse_table();
+        check_blocks(blocks, self.output_bits)?;
+
+        Ok(blocks
+            .iter()
+            .map(|&y| inverse_table[y as usize] as u64)
+            .collect())
+    }
+
+    fn substitute_value(table: &[u32], value: u64, n: usize, s_box_size: usize) -> u64 {
+        let mask = (1 << s_box_size) - 1;
+        (0..n / s_box_size).fold(0, |acc, j| {
+            let shift = n - (j + 1) * s_box_size;
+            acc | (table[((value >> shift) & mask) as usize] as u64) << shift
+        })
+    }
+
+    #[cfg(not(portable_simd))]
+    fn substitute_layer(table: &[u32], states: &mut [u64], n: usize, s_box_size: usize) {
+        for state in states {
+            *state = Self::substitute_value(table, *state, n, s_box_size);
+        }
+    }
+
+    #[cfg(portable_simd)]
+    fn substitute_layer(table: &[u32], states: &mut [u64], n: usize, s_box_size: usize) {
+        if s_box_size == 4 {
+            return crate::simd::substitute_nibbles(table, states, n);
+        }
+
+        for state in states {
+            *state = Self::substitute_value(table, *state, n, s_box_size);
+        }
+    }
+}
+
+impl PBox {
+    fn permute_value(permutation: &[u32], value: u64) -> u64 {
+        let n = permutation.len();
+        permutation.iter().enumerate().fold(0, |acc, (i, &num)| {
+            acc | ((value >> (n - 1 - i)) & 1) << (n - num as usize)
+        })
+    }
+}
+
+impl Spn {
+    fn value_keys(&self, blocks: &[u64]) -> Result<Vec<u64>, &'static str> {
+        if self.block_size() > 64 {
+            return Err("block size exceeds 64 bits");
+        }
+        check_blocks(blocks, self.block_size())?;
+
+        Ok(self
+            .round_keys()
+            .iter()
+            .map(|key| value_bits(key))
+            .collect())
+    }
+
+    pub fn encrypt_many(&self, blocks: &[u64]) -> Result<Vec<u64>, &'static str> {
+        let keys = self.value_keys(blocks)?;
+        let (n, s_box_size) = (self.block_size(), self.s_box().input_bits());
+        let (table, permutation) = (&self.s_box().table, &self.p_box().permutation);
+
+        let mut states = blocks.to_vec();
+        for &key in &keys[..self.rounds()] {
+            states.iter_mut().for_each(|state| *state ^= key);
+            SBox::substitute_layer(table, &mut states, n, s_box_size);
+            states
+                .iter_mut()
+                .for_each(|state| *state = PBox::permute_value(permutation, *state));
+        }
+        states
+            .iter_mut()
+            .for_each(|state| *state ^= keys[self.rounds()]);
+
+        Ok(states)
+    }
+
+    pub fn decrypt_many(&self, blocks: &[u64]) -> Result<Vec<u64>, &'static str> {
+        let keys = self.value_keys(blocks)?;
+        let (n, s_box_size) = (self.block_size(), self.s_box().input_bits());
+        let inverse_table = self.s_box().inverse_table();
+        let inverse_permutation = &self.p_box().inverse_permutation;
+
+        let mut states: Vec<u64> = blocks
+            .iter()
+            .map(|&block| block ^ keys[self.rounds()])
+            .collect();
+        for &key in keys[..self.rounds()].iter().rev() {
+            states
+                .iter_mut()
+                .for_each(|state| *state = PBox::permute_value(inverse_permutation, *state));
+            SBox::substitute_layer(inverse_table, &mut states, n, s_box_size);
+            states.iter_mut().for_each(|state| *state ^= key);
+        }
+
+        Ok(states)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_s_box, heys_spn};
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_encrypt_many() {
+        let s_box = heys_s_box();
+        assert_eq!(s_box.encrypt_many(&[0, 6, 15]), Ok(vec![14, 11, 7]));
+        assert_eq!(s_box.decrypt_many(&[14, 11, 7]), Ok(vec![0, 6, 15]));
+        assert!(s_box.encrypt_many(&[16]).is_err());
+        assert!(s_box.decrypt_many(&[u64::MAX]).is_err());
+
+        let spn = heys_spn();
+        let blocks: Vec<u64> = (0..1 << 16).step_by(97).collect();
+        let expected: Vec<u64> = blocks
+            .iter()
+            .map(|&x| bits2num(&spn.encrypt(&num2bits(x as u32, 16))) as u64)
+            .collect();
+        assert_eq!(spn.encrypt_many(&blocks).unwrap(), expected);
+        assert_eq!(spn.decrypt_many(&expected).unwrap(), blocks);
+        assert!(spn.encrypt_many(&[1 << 16]).is_err());
+        assert!(spn.decrypt_many(&[1 << 16]).is_err());
+
+        let present = Spn::present80(0xffff_ffff_ffff_ffff_ffff).unwrap();
+        let ciphertexts = present.encrypt_many(&[0, u64::MAX]).unwrap();
+        assert_eq!(ciphertexts, vec![0xe72c46c0f5945049, 0x3333dcd3213210d2]);
+        assert_eq!(present.decrypt_many(&ciphertexts), Ok(vec![0, u64::MAX]));
+
+        let wide = Spn::new(
+            SBox::from_flat((0..16).collect(), 4, 4).unwrap(),
+            PBox::new((1..=68).collect()).unwrap(),
+            vec![vec![false; 68]; 2],
+        )
+        .unwrap();
+        assert!(wide.encrypt_many(&[0]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use ps_blocks::{bits2num, num2bits, CHeader, CsvOptions, Radix, SBox};
+
+const USAGE: &str = "usage:
+    psboxs analyze <s-box file> [--json]
+    psboxs invert <s-box file>
+    psboxs generate <bits> [seed]
+    psboxs convert <s-box file> <csv|hex|c|rust|sage|pla|latex|verilog|vhdl>
+    psboxs encrypt <s-box file> <input file> <output file>
+    psboxs decrypt <s-box file> <input file> <output file>
+
+S-box files may contain a C array, a Sage SBox(...) call or a CSV table
+(decimal, or hexadecimal if decimal parsing fails).";
+
+fn load_s_box(path: &str) -> Result<SBox, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+
+    let result = if text.contains('{') {
+        SBox::from_c_array(&text)
+    } else if text.contains("SBox(") {
+        SBox::from_sage(&text)
+    } else {
+        SBox::from_csv(&text, &CsvOptions::default()).or_else(|_| {
+            let options = CsvOptions {
+                radix: Radix::Hexadecimal,
+                ..CsvOptions::default()
+            };
+            SBox::from_csv(&text, &options)
+        })
+    };
+
+    result.map_err(|err| format!("{}: {}", path, err))
+}
+
+fn generate(bits: usize, seed: u64) -> Result<SBox, &'static str> {
+    if bits == 0 || bits > 24 {
+        return Err("bits must be between 1 and 24");
+    }
+
+    let mut state = seed | 1;
+    let mut values: Vec<u32> = (0..1u32 << bits).collect();
+    for i in (1..values.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        values.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+
+    SBox::new(vec![values])
+}
+
+fn convert(s_box: &SBox, format: &str) -> Result<String, &'static str> {
+    let hex = CsvOptions {
+        radix: Radix::Hexadecimal,
+        ..CsvOptions::default()
+    };
+
+    Ok(match format {
+        "csv" => s_box.to_csv(&CsvOptions::default()),
+        "hex" => s_box.to_csv(&hex),
+        "c" => CHeader::new("s_box")
+            .add_s_box("s_box", s_box, true)
+            .render(),
+        "rust" => s_box.to_rust("s_box"),
+        "sage" => s_box.to_sage("S"),
+        "pla" => s_box.to_pla(),
+        "latex" => s_box.to_latex(&Default::default()),
+        "verilog" => s_box.to_verilog("s_box"),
+        "vhdl" => s_box.to_vhdl("s_box"),
+        _ => return Err("unknown format"),
+    })
+}
+
+fn transform_file(s_box: &SBox, input: &str, output: &str, decrypt: bool) -> Result<(), String> {
+    let bits = s_box.input_bits();
+    if (bits != 4 && bits != 8) || s_box.output_bits() != bits {
+        return Err("file encryption needs a 4-bit or 8-bit s-box".to_string());
+    }
+
+    let data = fs::read(input).map_err(|err| format!("{}: {}", input, err))?;
+    let apply = |value: u32| {
+        let value_bits = num2bits(value, bits);
+        let result = if decrypt {
+            s_box.decrypt(&value_bits)
+        } else {
+            s_box.encrypt(&value_bits)
+        };
+        bits2num(&result) as u8
+    };
+
+    let result: Vec<u8> = data
+        .iter()
+        .map(|&byte| match bits {
+            8 => apply(byte as u32),
+            _ => (apply(byte as u32 >> 4) << 4) | apply(byte as u32 & 0xf),
+        })
+        .collect();
+
+    fs::write(output, result).map_err(|err| format!("{}: {}", output, err))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args[..] {
+        ["analyze", path] => println!("{}", load_s_box(path)?.report()),
+        ["analyze", path, "--json"] => println!("{}", load_s_box(path)?.report().to_json()),
+        ["invert", path] => {
+            let inverse = load_s_box(path)?.inverse()?;
+            print!("{}", inverse.to_csv(&CsvOptions::default()));
+        }
+        ["generate", bits] | ["generate", bits, _] => {
+            let bits = bits.parse().map_err(|_| "invalid bit count")?;
+            let seed = match args.get(2) {
+                Some(seed) => seed.parse().map_err(|_| "invalid seed")?,
+                None => 0x2545f4914f6cdd1d,
+            };
+            print!("{}", generate(bits, seed)?.to_csv(&CsvOptions::default()));
+        }
+        ["convert", path, format] => print!("{}", convert(&load_s_box(path)?, format)?),
+        ["encrypt", path, input, output] => {
+            transform_file(&load_s_box(path)?, input, output, false)?
+        }
+        ["decrypt", path, input, output] => {
+            transform_file(&load_s_box(path)?, input, output, true)?
+        }
+        _ => return Err(USAGE.to_string()),
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
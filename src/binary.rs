@@ -0,0 +1,227 @@
+use crate::{PBox, SBox, Spn};
+
+const MAGIC: &[u8; 4] = b"PSBX";
+const VERSION: u8 = 1;
+const MSB_FIRST: u8 = 0;
+
+const KIND_S_BOX: u8 = 1;
+const KIND_P_BOX: u8 = 2;
+const KIND_SPN: u8 = 3;
+
+fn header(kind: u8) -> Vec<u8> {
+    let mut result = MAGIC.to_vec();
+    result.extend([VERSION, kind, MSB_FIRST]);
+
+    result
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], kind: u8) -> Result<Reader<'a>, &'static str> {
+        let mut reader = Reader { bytes };
+        if reader.take(4)? != MAGIC {
+            return Err("invalid magic");
+        }
+        if reader.byte()? != VERSION {
+            return Err("unsupported version");
+        }
+        if reader.byte()? != kind {
+            return Err("unexpected object kind");
+        }
+        if reader.byte()? != MSB_FIRST {
+            return Err("unsupported bit order");
+        }
+
+        Ok(reader)
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], &'static str> {
+        if self.bytes.len() < count {
+            return Err("unexpected end of data");
+        }
+
+        let (result, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(result)
+    }
+
+    fn byte(&mut self) -> Result<u8, &'static str> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn uint(&mut self, width: usize) -> Result<u32, &'static str> {
+        Ok(self
+            .take(width)?
+            .iter()
+            .rev()
+            .fold(0, |acc, &byte| (acc << 8) | byte as u32))
+    }
+
+    fn finish(&self) -> Result<(), &'static str> {
+        if !self.bytes.is_empty() {
+            return Err("trailing data");
+        }
+
+        Ok(())
+    }
+}
+
+fn push_uint(bytes: &mut Vec<u8>, value: u32, width: usize) {
+    bytes.extend(&value.to_le_bytes()[..width]);
+}
+
+fn value_width(bit_count: usize) -> usize {
+    bit_count.div_ceil(8).max(1)
+}
+
+impl SBox {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = header(KIND_S_BOX);
+        result.extend([self.input_bits as u8, self.output_bits as u8]);
+
+        let width = value_width(self.output_bits);
+        for &value in &self.table {
+            push_uint(&mut result, value, width);
+        }
+
+        result
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<SBox, &'static str> {
+        let mut reader = Reader::new(bytes, KIND_S_BOX)?;
+        let input_bits = reader.byte()? as usize;
+        let output_bits = reader.byte()? as usize;
+        if input_bits >= 32 || output_bits >= 32 {
+            return Err("invalid table");
+        }
+
+        let width = value_width(output_bits);
+        let table = (0..1usize << input_bits)
+            .map(|_| reader.uint(width))
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+
+        let s_box = SBox::new(vec![table])?;
+        if s_box.output_bits != output_bits {
+            return Err("table values do not match output bits");
+        }
+
+        Ok(s_box)
+    }
+}
+
+impl PBox {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, &'static str> {
+        if self.len() > u16::MAX as usize {
+            return Err("p-box is too large for the binary format");
+        }
+
+        let mut result = header(KIND_P_BOX);
+        push_uint(&mut result, self.permutation.len() as u32, 2);
+        for &num in &self.permutation {
+            push_uint(&mut result, num, 2);
+        }
+
+        Ok(result)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<PBox, &'static str> {
+        let mut reader = Reader::new(bytes, KIND_P_BOX)?;
+        let n = reader.uint(2)?;
+        let permutation = (0..n)
+            .map(|_| reader.uint(2))
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+
+        PBox::new(permutation)
+    }
+}
+
+impl Spn {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, &'static str> {
+        if self.round_keys().len() > u16::MAX as usize {
+            return Err("too many round keys for the binary format");
+        }
+
+        let mut result = header(KIND_SPN);
+        for part in [self.s_box().to_bytes(), self.p_box().to_bytes()?] {
+            push_uint(&mut result, part.len() as u32, 4);
+            result.extend(part);
+        }
+
+        result.push(self.has_t_tables() as u8);
+        push_uint(&mut result, self.round_keys().len() as u32, 2);
+        for key in self.round_keys() {
+            for chunk in key.chunks(8) {
+                let byte = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |acc, (i, &bit)| acc | ((bit as u8) << (7 - i)));
+                result.push(byte);
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Spn, &'static str> {
+        let mut reader = Reader::new(bytes, KIND_SPN)?;
+        let s_box_len = reader.uint(4)? as usize;
+        let s_box = SBox::from_bytes(reader.take(s_box_len)?)?;
+        let p_box_len = reader.uint(4)? as usize;
+        let p_box = PBox::from_bytes(reader.take(p_box_len)?)?;
+
+        let t_tables = reader.byte()? != 0;
+        let key_count = reader.uint(2)?;
+        let block_size = p_box.len();
+        let round_keys = (0..key_count)
+            .map(|_| {
+                let key_bytes = reader.take(block_size.div_ceil(8))?;
+                Ok((0..block_size)
+                    .map(|i| (key_bytes[i / 8] >> (7 - i % 8)) & 1 == 1)
+                    .collect())
+            })
+            .collect::<Result<Vec<_>, &'static str>>()?;
+        reader.finish()?;
+
+        let spn = Spn::new(s_box, p_box, round_keys)?;
+        match t_tables {
+            true => spn.with_t_tables(),
+            false => Ok(spn),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_spn;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_binary() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let bytes = s_box.to_bytes();
+        assert_eq!(bytes, b"PSBX\x01\x01\x00\x02\x02\x02\x00\x03\x01");
+        assert_eq!(SBox::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+        assert!(SBox::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PBox::from_bytes(&bytes).is_err());
+
+        let spn = heys_spn();
+
+        let restored = Spn::from_bytes(&spn.to_bytes().unwrap()).unwrap();
+        let a = num2bits(0x26b7, 16);
+        assert_eq!(restored.encrypt(&a), spn.encrypt(&a));
+        assert_eq!(bits2num(&restored.round_keys()[1]), 0xa94d);
+
+        let present = Spn::present80(0).unwrap();
+        let restored = Spn::from_bytes(&present.to_bytes().unwrap()).unwrap();
+        assert!(restored.p_box() == present.p_box());
+
+        let huge = PBox::new((1..=1 << 16).collect()).unwrap();
+        assert!(huge.to_bytes().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{MiniAes, SDes, Spn};
+
+pub(crate) fn xor(a: &[bool], b: &[bool]) -> Vec<bool> {
+    a.iter().zip(b).map(|(&x, &y)| x ^ y).collect()
+}
+
+pub trait BlockCipher {
+    fn block_size(&self) -> usize;
+    fn encrypt_block(&self, bits: &[bool]) -> Vec<bool>;
+    fn decrypt_block(&self, bits: &[bool]) -> Vec<bool>;
+}
+
+impl<C: BlockCipher + ?Sized> BlockCipher for &C {
+    fn block_size(&self) -> usize {
+        (**self).block_size()
+    }
+
+    fn encrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        (**self).encrypt_block(bits)
+    }
+
+    fn decrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        (**self).decrypt_block(bits)
+    }
+}
+
+impl BlockCipher for Spn {
+    fn block_size(&self) -> usize {
+        Spn::block_size(self)
+    }
+
+    fn encrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        self.encrypt(bits)
+    }
+
+    fn decrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        self.decrypt(bits)
+    }
+}
+
+impl BlockCipher for MiniAes {
+    fn block_size(&self) -> usize {
+        16
+    }
+
+    fn encrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        self.encrypt(bits)
+    }
+
+    fn decrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        self.decrypt(bits)
+    }
+}
+
+impl BlockCipher for SDes {
+    fn block_size(&self) -> usize {
+        8
+    }
+
+    fn encrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        self.encrypt(bits)
+    }
+
+    fn decrypt_block(&self, bits: &[bool]) -> Vec<bool> {
+        self.decrypt(bits)
+    }
+}
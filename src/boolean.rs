@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+
+use crate::analysis::walsh_hadamard;
+use crate::{ConstantTimeSBox, SBox};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BooleanFunction {
+    variables: usize,
+    truth_table: Vec<bool>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComponentClass {
+    Bent,
+    SemiBent,
+    Plateaued(u32),
+    Other,
+}
+
+pub struct ComponentSummary {
+    pub mask: u32,
+    pub class: ComponentClass,
+    pub walsh_distribution: BTreeMap<i32, usize>,
+}
+
+impl BooleanFunction {
+    pub fn new(truth_table: Vec<bool>) -> Result<BooleanFunction, &'static str> {
+        let n = truth_table.len();
+        if n == 0 || !n.is_power_of_two() {
+            return Err("truth table length is not a power of two");
+        }
+
+        Ok(BooleanFunction {
+            variables: n.trailing_zeros() as usize,
+            truth_table,
+        })
+    }
+
+    pub fn from_fn(variables: usize, f: impl Fn(u32) -> bool) -> BooleanFunction {
+        BooleanFunction {
+            variables,
+            truth_table: (0..1u32 << variables).map(f).collect(),
+        }
+    }
+
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    pub fn truth_table(&self) -> &[bool] {
+        &self.truth_table
+    }
+
+    pub fn evaluate(&self, x: u32) -> bool {
+        self.truth_table[x as usize]
+    }
+
+    pub fn weight(&self) -> usize {
+        self.truth_table.iter().filter(|&&bit| bit).count()
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        2 * self.weight() == self.truth_table.len()
+    }
+
+    pub fn anf(&self) -> Vec<bool> {
+        let table: Vec<u32> = self.truth_table.iter().map(|&bit| bit as u32).collect();
+        ConstantTimeSBox::moebius(&table)
+            .into_iter()
+            .map(|c| c == 1)
+            .collect()
+    }
+
+    pub fn degree(&self) -> usize {
+        self.anf()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c)
+            .map(|(monomial, _)| monomial.count_ones() as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn monomial_count(&self) -> usize {
+        self.anf().iter().filter(|&&c| c).count()
+    }
+
+    pub fn walsh_spectrum(&self) -> Vec<i32> {
+        let mut spectrum: Vec<i32> = self
+            .truth_table
+            .iter()
+            .map(|&bit| if bit { -1 } else { 1 })
+            .collect();
+        walsh_hadamard(&mut spectrum);
+
+        spectrum
+    }
+
+    pub fn nonlinearity(&self) -> usize {
+        let max = self
+            .walsh_spectrum()
+            .iter()
+            .map(|w| w.unsigned_abs() as usize)
+            .max()
+            .unwrap_or(0);
+
+        (self.truth_table.len() - max) / 2
+    }
+
+    pub fn is_bent(&self) -> bool {
+        let amplitude = 1 << (self.variables / 2);
+        self.variables.is_multiple_of(2)
+            && self
+                .walsh_spectrum()
+                .iter()
+                .all(|w| w.unsigned_abs() == amplitude)
+    }
+
+    pub fn is_semi_bent(&self) -> bool {
+        let amplitude = 1 << (self.variables / 2 + 1);
+        self.walsh_spectrum()
+            .iter()
+            .all(|w| *w == 0 || w.unsigned_abs() == amplitude)
+    }
+
+    pub fn plateau_amplitude(&self) -> Option<u32> {
+        let spectrum = self.walsh_spectrum();
+        let amplitude = spectrum.iter().map(|w| w.unsigned_abs()).max()?;
+        spectrum
+            .iter()
+            .all(|w| *w == 0 || w.unsigned_abs() == amplitude)
+            .then_some(amplitude)
+    }
+
+    pub fn classify(&self) -> ComponentClass {
+        if self.is_bent() {
+            ComponentClass::Bent
+        } else if self.is_semi_bent() {
+            ComponentClass::SemiBent
+        } else if let Some(amplitude) = self.plateau_amplitude() {
+            ComponentClass::Plateaued(amplitude)
+        } else {
+            ComponentClass::Other
+        }
+    }
+
+    pub fn correlation_immunity(&self) -> usize {
+        let spectrum = self.walsh_spectrum();
+        (1..=self.variables)
+            .find(|&t| {
+                spectrum
+                    .iter()
+                    .enumerate()
+                    .any(|(a, &w)| a.count_ones() as usize == t && w != 0)
+            })
+            .map_or(self.variables, |t| t - 1)
+    }
+
+    fn has_annihilator(&self, value: bool, degree: usize) -> bool {
+        let monomials: Vec<usize> = (0..self.truth_table.len())
+            .filter(|m| m.count_ones() as usize <= degree)
+            .collect();
+
+        let mut rows: Vec<Vec<bool>> = (0..self.truth_table.len())
+            .filter(|&x| self.truth_table[x] == value)
+            .map(|x| monomials.iter().map(|&m| x & m == m).collect())
+            .collect();
+
+        let mut rank = 0;
+        for column in 0..monomials.len() {
+            let Some(pivot) = (rank..rows.len()).find(|&i| rows[i][column]) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let pivot_row = rows[rank].clone();
+            for row in rows.iter_mut().skip(rank + 1) {
+                if row[column] {
+                    for (el, &p) in row.iter_mut().zip(&pivot_row) {
+                        *el ^= p;
+                    }
+                }
+            }
+            rank += 1;
+        }
+
+        rank < monomials.len()
+    }
+
+    pub fn algebraic_immunity(&self) -> usize {
+        (0..=self.variables)
+            .find(|&degree| {
+                self.has_annihilator(true, degree) || self.has_annihilator(false, degree)
+            })
+            .unwrap_or(self.variables)
+    }
+}
+
+impl SBox {
+    pub fn component(&self, mask: u32) -> BooleanFunction {
+        BooleanFunction::from_fn(self.input_bits, |x| {
+            (self.table[x as usize] & mask).count_ones() & 1 == 1
+        })
+    }
+
+    pub fn coordinate(&self, index: usize) -> BooleanFunction {
+        self.component(1 << (self.output_bits - 1 - index))
+    }
+
+    pub fn components(&self) -> impl Iterator<Item = BooleanFunction> + '_ {
+        (1..1u32 << self.output_bits).map(|mask| self.component(mask))
+    }
+
+    pub fn anf_monomial_counts(&self) -> Vec<usize> {
+        (0..self.output_bits)
+            .map(|i| self.coordinate(i).monomial_count())
+            .collect()
+    }
+
+    pub fn anf_monomial_count(&self) -> usize {
+        self.anf_monomial_counts().iter().sum()
+    }
+
+    pub fn classify_components(&self) -> Vec<ComponentSummary> {
+        (1..1u32 << self.output_bits)
+            .map(|mask| {
+                let component = self.component(mask);
+                let mut walsh_distribution = BTreeMap::new();
+                for w in component.walsh_spectrum() {
+                    *walsh_distribution.entry(w).or_insert(0) += 1;
+                }
+
+                ComponentSummary {
+                    mask,
+                    class: component.classify(),
+                    walsh_distribution,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::present_s_box;
+
+    #[test]
+    fn test_boolean_function() {
+        let f = BooleanFunction::new(vec![false, true, true, false]).unwrap();
+        assert_eq!(f.variables(), 2);
+        assert!(f.evaluate(0b01));
+        assert_eq!(f.weight(), 2);
+        assert!(f == BooleanFunction::from_fn(2, |x| x.count_ones() == 1));
+        assert!(BooleanFunction::new(vec![false; 3]).is_err());
+        assert!(BooleanFunction::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_analysis() {
+        let bent =
+            BooleanFunction::from_fn(4, |x| ((x >> 3) & (x >> 2) & 1) ^ ((x >> 1) & x & 1) == 1);
+        assert!(bent.is_bent());
+        assert!(!bent.is_balanced());
+        assert_eq!(bent.degree(), 2);
+        assert_eq!(bent.nonlinearity(), 6);
+        assert_eq!(bent.monomial_count(), 2);
+
+        let majority = BooleanFunction::from_fn(3, |x| x.count_ones() >= 2);
+        assert!(majority.is_balanced());
+        assert!(majority.is_semi_bent());
+        assert_eq!(majority.degree(), 2);
+        assert_eq!(majority.correlation_immunity(), 0);
+        assert_eq!(majority.algebraic_immunity(), 2);
+
+        let parity = BooleanFunction::from_fn(3, |x| x.count_ones() % 2 == 1);
+        assert_eq!(parity.correlation_immunity(), 2);
+        assert_eq!(parity.nonlinearity(), 0);
+        assert_eq!(parity.algebraic_immunity(), 1);
+        assert!(!parity.is_semi_bent());
+
+        let zero = BooleanFunction::from_fn(2, |_| false);
+        assert_eq!(zero.degree(), 0);
+        assert_eq!(zero.algebraic_immunity(), 0);
+    }
+
+    #[test]
+    fn test_classify_components() {
+        let present = present_s_box();
+        let summaries = present.classify_components();
+        assert_eq!(summaries.len(), 15);
+        let semi_bent: Vec<u32> = summaries
+            .iter()
+            .filter(|summary| summary.class == ComponentClass::SemiBent)
+            .map(|summary| summary.mask)
+            .collect();
+        assert_eq!(semi_bent, vec![0x1, 0xa, 0xb]);
+        assert_eq!(
+            summaries[0].walsh_distribution,
+            BTreeMap::from([(-8, 1), (0, 12), (8, 3)])
+        );
+        assert_eq!(summaries[1].class, ComponentClass::Other);
+
+        let quadratic = SBox::from_flat(vec![0, 1, 2, 3, 4, 5, 7, 6], 3, 3).unwrap();
+        assert_eq!(
+            quadratic.component(0b001).classify(),
+            ComponentClass::SemiBent
+        );
+        assert_eq!(
+            quadratic.component(0b100).classify(),
+            ComponentClass::Plateaued(8)
+        );
+        let bent = BooleanFunction::from_fn(2, |x| x == 3);
+        assert_eq!(bent.classify(), ComponentClass::Bent);
+        let other = BooleanFunction::from_fn(3, |x| x == 0);
+        assert_eq!(other.classify(), ComponentClass::Other);
+    }
+
+    #[test]
+    fn test_components() {
+        let s_box = SBox::from_flat(vec![2, 0, 3, 1], 2, 2).unwrap();
+        assert_eq!(
+            s_box.coordinate(0).truth_table(),
+            [true, false, true, false]
+        );
+        assert_eq!(
+            s_box.coordinate(1).truth_table(),
+            [false, false, true, true]
+        );
+        assert_eq!(
+            s_box.component(0b11).truth_table(),
+            [true, false, false, true]
+        );
+        assert_eq!(s_box.components().count(), 3);
+        assert_eq!(s_box.anf_monomial_counts(), vec![2, 1]);
+        assert_eq!(s_box.anf_monomial_count(), 3);
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use crate::{DifferentialTrail, Spn};
+
+pub struct Boomerang {
+    pub upper: DifferentialTrail,
+    pub lower: DifferentialTrail,
+    pub probability: f64,
+}
+
+fn lightest_per_difference(
+    trails: Vec<DifferentialTrail>,
+    difference: impl Fn(&DifferentialTrail) -> u32,
+) -> Vec<DifferentialTrail> {
+    let mut seen = HashSet::new();
+    trails
+        .into_iter()
+        .filter(|trail| seen.insert(difference(trail)))
+        .collect()
+}
+
+impl Spn {
+    pub fn boomerang_probability(
+        &self,
+        upper: &DifferentialTrail,
+        lower: &DifferentialTrail,
+    ) -> Result<f64, &'static str> {
+        self.check_word_size()?;
+
+        let s_box_size = self.s_box().input_bits();
+        let middle_input = *upper.differences.last().unwrap();
+        let middle_output = self.p_box().decrypt_value(lower.differences[0]);
+
+        let switch: f64 = (0..self.s_box_count())
+            .map(|j| {
+                let a = self.chunk(middle_input, j);
+                let b = self.chunk(middle_output, j);
+                self.s_box().bct_row(a)[b as usize] as f64 / (1 << s_box_size) as f64
+            })
+            .product();
+
+        Ok((upper.probability() * lower.probability()).powi(2) * switch)
+    }
+
+    pub fn best_boomerang(&self, upper_rounds: usize, lower_rounds: usize) -> Option<Boomerang> {
+        if upper_rounds + lower_rounds + 1 > self.rounds() {
+            return None;
+        }
+
+        let max_slack = (upper_rounds.max(lower_rounds) * self.block_size()) as f64;
+        let mut slack = 0.0;
+        loop {
+            let mut uppers = lightest_per_difference(
+                self.differential_trails_within(upper_rounds, slack)?,
+                |trail| *trail.differences.last().unwrap(),
+            );
+            let mut lowers = lightest_per_difference(
+                self.differential_trails_within(lower_rounds, slack)?,
+                |trail| trail.differences[0],
+            );
+
+            let mut best = (0.0, 0, 0);
+            for (i, upper) in uppers.iter().enumerate() {
+                for (j, lower) in lowers.iter().enumerate() {
+                    let probability = self.boomerang_probability(upper, lower).ok()?;
+                    if probability > best.0 {
+                        best = (probability, i, j);
+                    }
+                }
+            }
+
+            // Pairs that are not enumerated yet have a trail heavier than its optimum plus slack.
+            let bound = (-2.0 * (uppers[0].weight + lowers[0].weight + slack)).exp2();
+            if (best.0 > 0.0 && best.0 >= bound) || slack >= max_slack {
+                let (probability, i, j) = best;
+                return (probability > 0.0).then(|| Boomerang {
+                    upper: uppers.swap_remove(i),
+                    lower: lowers.swap_remove(j),
+                    probability,
+                });
+            }
+            slack += 1.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{heys_p_box, heys_s_box};
+    use crate::{num2bits, DifferentialTrail, PBox, Spn};
+
+    fn layer_weights(spn: &Spn) -> Vec<Vec<f64>> {
+        let ddt = spn.s_box().ddt();
+        let n = spn.block_size();
+
+        (0..1u32 << n)
+            .map(|x| {
+                (0..1u32 << n)
+                    .map(|z| {
+                        (0..spn.s_box_count())
+                            .map(|j| {
+                                let count = ddt[spn.chunk(x, j) as usize][spn.chunk(z, j) as usize];
+                                4.0 - (count as f64).log2()
+                            })
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_boomerang() {
+        let spn = Spn::new(heys_s_box(), heys_p_box(), vec![num2bits(0, 16); 5]).unwrap();
+
+        let boomerang = spn.best_boomerang(1, 2).unwrap();
+        let expected = (boomerang.upper.probability() * boomerang.lower.probability()).powi(2);
+        assert!(boomerang.probability > 0.0);
+        assert!(boomerang.probability <= expected);
+        assert!(spn.best_boomerang(2, 2).is_none());
+    }
+
+    #[test]
+    fn test_incompatible_best_trails() {
+        let p_box = PBox::new(vec![1, 5, 2, 6, 3, 7, 4, 8]).unwrap();
+        let spn = Spn::new(heys_s_box(), p_box, vec![num2bits(0, 8); 5]).unwrap();
+
+        let upper = spn.best_differential_trail(2).unwrap();
+        let lower = spn.best_differential_trail(1).unwrap();
+        assert_eq!(spn.boomerang_probability(&upper, &lower), Ok(0.0));
+
+        let weights = layer_weights(&spn);
+        let p_box = spn.p_box();
+        let mut upper_weights = vec![0.0; 256];
+        upper_weights[0] = f64::INFINITY;
+        for _ in 0..2 {
+            let mut next = vec![f64::INFINITY; 256];
+            for (x, row) in weights.iter().enumerate().skip(1) {
+                for (z, &weight) in row.iter().enumerate().skip(1) {
+                    let y = p_box.encrypt_value(z as u32) as usize;
+                    next[y] = next[y].min(upper_weights[x] + weight);
+                }
+            }
+            upper_weights = next;
+        }
+        let lower_weights: Vec<f64> = (0..256)
+            .map(|x| match x {
+                0 => f64::INFINITY,
+                _ => (1..256)
+                    .map(|z| weights[x][z])
+                    .fold(f64::INFINITY, f64::min),
+            })
+            .collect();
+
+        let mut expected = 0.0f64;
+        for (a, &w) in upper_weights
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.is_finite())
+        {
+            for (d, &v) in lower_weights
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| v.is_finite())
+            {
+                let upper = DifferentialTrail {
+                    differences: vec![0, a as u32],
+                    weight: w,
+                };
+                let lower = DifferentialTrail {
+                    differences: vec![d as u32, 0],
+                    weight: v,
+                };
+                expected = expected.max(spn.boomerang_probability(&upper, &lower).unwrap());
+            }
+        }
+
+        let boomerang = spn.best_boomerang(2, 1).unwrap();
+        assert!(expected > 0.0);
+        assert!((boomerang.probability - expected).abs() < 1e-12);
+        assert_eq!(
+            spn.boomerang_probability(&boomerang.upper, &boomerang.lower),
+            Ok(boomerang.probability)
+        );
+    }
+
+    #[test]
+    fn test_present_is_rejected() {
+        let spn = Spn::present80(0).unwrap();
+        let trail = DifferentialTrail {
+            differences: vec![1, 1],
+            weight: 0.0,
+        };
+
+        assert!(spn.best_boomerang(1, 1).is_none());
+        assert!(spn.boomerang_probability(&trail, &trail).is_err());
+    }
+}
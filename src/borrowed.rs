@@ -0,0 +1,71 @@
+use crate::{bits2num, num2bits};
+
+pub struct SBoxRef<'a> {
+    table: &'a [u32],
+    bits: usize,
+}
+
+impl<'a> SBoxRef<'a> {
+    pub fn new(table: &'a [u32]) -> Result<SBoxRef<'a>, &'static str> {
+        let n = table.len();
+        if n < 2 || !n.is_power_of_two() {
+            return Err("invalid table");
+        }
+        if table.iter().any(|&y| y as usize >= n) {
+            return Err("invalid table");
+        }
+
+        Ok(SBoxRef {
+            table,
+            bits: n.trailing_zeros() as usize,
+        })
+    }
+
+    pub fn from_rows<const M: usize>(rows: &'a [[u32; M]]) -> Result<SBoxRef<'a>, &'static str> {
+        Self::new(rows.as_flattened())
+    }
+
+    pub fn input_bits(&self) -> usize {
+        self.bits
+    }
+
+    pub fn output_bits(&self) -> usize {
+        self.bits
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(self.table[bits2num(bits) as usize], self.bits)
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Option<Vec<bool>> {
+        let y = bits2num(bits);
+        let x = self.table.iter().position(|&el| el == y)?;
+
+        Some(num2bits(x as u32, self.bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_table;
+
+    #[test]
+    fn test_s_box_ref() {
+        let rows: Vec<[u32; 4]> = heys_table()
+            .chunks(4)
+            .map(|row| row.try_into().unwrap())
+            .collect();
+        let s_box = SBoxRef::from_rows(&rows).unwrap();
+        assert_eq!(s_box.input_bits(), 4);
+
+        let a = num2bits(0b0110, 4);
+        let b = s_box.encrypt(&a);
+        assert_eq!(bits2num(&b), 0xb);
+        assert_eq!(s_box.decrypt(&b), Some(a));
+
+        assert!(SBoxRef::new(&[0, 1, 2]).is_err());
+        assert!(SBoxRef::new(&[0, 1, 2, 4]).is_err());
+        assert_eq!(SBoxRef::new(&[0, 0]).unwrap().decrypt(&[true]), None);
+    }
+}
@@ -0,0 +1,225 @@
+#[cfg(feature = "config")]
+use serde::Deserialize;
+
+use crate::{PBox, SBox};
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "config", derive(Deserialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "kebab-case"))]
+pub enum BitOrder {
+    #[default]
+    MsbFirst,
+    LsbFirst,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Indexing {
+    ZeroBased,
+    #[default]
+    OneBased,
+}
+
+fn reverse_bits(num: u32, bit_count: usize) -> u32 {
+    if bit_count == 0 {
+        return 0;
+    }
+
+    num.reverse_bits() >> (32 - bit_count)
+}
+
+#[derive(Default)]
+pub struct SBoxBuilder {
+    input_bits: Option<usize>,
+    output_bits: Option<usize>,
+    bit_order: BitOrder,
+    values: Option<Vec<u32>>,
+}
+
+impl SBoxBuilder {
+    pub fn new() -> SBoxBuilder {
+        SBoxBuilder::default()
+    }
+
+    pub fn input_bits(mut self, input_bits: usize) -> SBoxBuilder {
+        self.input_bits = Some(input_bits);
+        self
+    }
+
+    pub fn output_bits(mut self, output_bits: usize) -> SBoxBuilder {
+        self.output_bits = Some(output_bits);
+        self
+    }
+
+    pub fn bit_order(mut self, bit_order: BitOrder) -> SBoxBuilder {
+        self.bit_order = bit_order;
+        self
+    }
+
+    pub fn values(mut self, values: Vec<u32>) -> SBoxBuilder {
+        self.values = Some(values);
+        self
+    }
+
+    pub fn build(self) -> Result<SBox, &'static str> {
+        let input_bits = self.input_bits.ok_or("input width is not set")?;
+        let output_bits = self.output_bits.ok_or("output width is not set")?;
+        let mut values = self.values.ok_or("values are not set")?;
+        if input_bits >= 32 || output_bits >= 32 {
+            return Err("widths must be below 32 bits");
+        }
+        if values.len() != 1 << input_bits {
+            return Err("value count does not match input width");
+        }
+        if values.iter().any(|&y| y >> output_bits != 0) {
+            return Err("value does not fit output width");
+        }
+        if input_bits != output_bits {
+            return Err("output width must equal input width");
+        }
+
+        if self.bit_order == BitOrder::LsbFirst {
+            let mut reversed = vec![0; values.len()];
+            for (x, &y) in values.iter().enumerate() {
+                reversed[reverse_bits(x as u32, input_bits) as usize] =
+                    reverse_bits(y, output_bits);
+            }
+            values = reversed;
+        }
+        if output_bits > 0 && values.iter().all(|&y| y >> (output_bits - 1) == 0) {
+            return Err("values never use the top output bit");
+        }
+
+        SBox::from_flat(values, input_bits, output_bits)
+    }
+}
+
+#[derive(Default)]
+pub struct PBoxBuilder {
+    width: Option<usize>,
+    bit_order: BitOrder,
+    indexing: Indexing,
+    permutation: Option<Vec<u32>>,
+}
+
+impl PBoxBuilder {
+    pub fn new() -> PBoxBuilder {
+        PBoxBuilder::default()
+    }
+
+    pub fn width(mut self, width: usize) -> PBoxBuilder {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn bit_order(mut self, bit_order: BitOrder) -> PBoxBuilder {
+        self.bit_order = bit_order;
+        self
+    }
+
+    pub fn indexing(mut self, indexing: Indexing) -> PBoxBuilder {
+        self.indexing = indexing;
+        self
+    }
+
+    pub fn permutation(mut self, permutation: Vec<u32>) -> PBoxBuilder {
+        self.permutation = Some(permutation);
+        self
+    }
+
+    pub fn build(self) -> Result<PBox, &'static str> {
+        let mut permutation = self.permutation.ok_or("permutation is not set")?;
+        let n = permutation.len() as u32;
+        if self.width.is_some_and(|width| width != permutation.len()) {
+            return Err("permutation length does not match width");
+        }
+
+        if self.indexing == Indexing::ZeroBased {
+            if permutation.iter().any(|&num| num >= n) {
+                return Err("zero-based index out of range");
+            }
+            permutation.iter_mut().for_each(|num| *num += 1);
+        }
+        if permutation.iter().any(|&num| num == 0 || num > n) {
+            return Err("one-based index out of range");
+        }
+
+        if self.bit_order == BitOrder::LsbFirst {
+            permutation = permutation.iter().rev().map(|&num| n - num + 1).collect();
+        }
+
+        PBox::new(permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_s_box_builder() {
+        let s_box = SBoxBuilder::new()
+            .input_bits(2)
+            .output_bits(2)
+            .bit_order(BitOrder::LsbFirst)
+            .values(vec![1, 2, 3, 0])
+            .build()
+            .unwrap();
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0b10, 2))), 0b01);
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0b01, 2))), 0b11);
+
+        let builder = || SBoxBuilder::new().input_bits(2).values(vec![1, 2, 3, 0]);
+        assert_eq!(builder().build().unwrap_err(), "output width is not set");
+        assert_eq!(
+            builder().output_bits(1).build().unwrap_err(),
+            "value does not fit output width"
+        );
+        assert_eq!(
+            builder().input_bits(3).output_bits(2).build().unwrap_err(),
+            "value count does not match input width"
+        );
+        assert_eq!(
+            builder().output_bits(3).build().unwrap_err(),
+            "output width must equal input width"
+        );
+        assert_eq!(
+            builder()
+                .output_bits(2)
+                .values(vec![0, 0, 1, 1])
+                .build()
+                .unwrap_err(),
+            "values never use the top output bit"
+        );
+    }
+
+    #[test]
+    fn test_p_box_builder() {
+        let p_box = PBoxBuilder::new()
+            .width(4)
+            .indexing(Indexing::ZeroBased)
+            .permutation(vec![1, 2, 3, 0])
+            .build()
+            .unwrap();
+        assert!(p_box == PBox::new(vec![2, 3, 4, 1]).unwrap());
+
+        let p_box = PBoxBuilder::new()
+            .bit_order(BitOrder::LsbFirst)
+            .permutation(vec![2, 3, 4, 1])
+            .build()
+            .unwrap();
+        assert_eq!(
+            p_box.encrypt(&[false, false, false, true]),
+            [false, false, true, false]
+        );
+
+        let builder = || PBoxBuilder::new().permutation(vec![1, 2, 3, 4]);
+        assert_eq!(
+            builder().width(8).build().unwrap_err(),
+            "permutation length does not match width"
+        );
+        assert_eq!(
+            builder().indexing(Indexing::ZeroBased).build().unwrap_err(),
+            "zero-based index out of range"
+        );
+    }
+}
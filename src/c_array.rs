@@ -0,0 +1,103 @@
+use crate::SBox;
+
+fn strip_comments(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map_or("", |end| &after[end..]);
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map_or("", |end| &after[end + 2..]);
+            result.push(' ');
+        } else {
+            let ch = rest.chars().next().unwrap();
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+
+    result
+}
+
+fn parse_c_number(token: &str) -> Result<u32, &'static str> {
+    let token = token.trim_end_matches(['u', 'U', 'l', 'L']);
+    let result = if let Some(digits) = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+    {
+        u32::from_str_radix(digits, 16)
+    } else if token.len() > 1 && token.starts_with('0') {
+        u32::from_str_radix(&token[1..], 8)
+    } else {
+        token.parse()
+    };
+
+    result.map_err(|_| "invalid number in c array")
+}
+
+fn declared_size(declaration: &str) -> Result<Option<usize>, &'static str> {
+    let mut size = None;
+    let mut rest = declaration;
+    while let Some(start) = rest.find('[') {
+        let end = rest[start..].find(']').ok_or("unbalanced brackets")? + start;
+        let dimension = rest[start + 1..end].trim();
+        if !dimension.is_empty() {
+            size = Some(size.unwrap_or(1) * parse_c_number(dimension)? as usize);
+        }
+        rest = &rest[end + 1..];
+    }
+
+    Ok(size)
+}
+
+impl SBox {
+    pub fn from_c_array(snippet: &str) -> Result<SBox, &'static str> {
+        let text = strip_comments(snippet);
+
+        let start = text.find('{').ok_or("no initializer found")?;
+        let end = text.rfind('}').ok_or("no initializer found")?;
+        if end < start {
+            return Err("no initializer found");
+        }
+
+        let values = text[start + 1..end]
+            .split(|ch: char| ch == ',' || ch == '{' || ch == '}' || ch.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(parse_c_number)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(size) = declared_size(&text[..start])? {
+            if size != values.len() {
+                return Err("number of values does not match declared size");
+            }
+        }
+
+        SBox::new(vec![values])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_from_c_array() {
+        let s_box = SBox::from_c_array(
+            "/* Heys */ static const uint8_t sbox[16] = {
+                0xE, 0x4, 0xD, 0x1, 0x2, 0xF, 0xB, 0x8, // first half
+                3, 10, 6, 12, 5, 9, 00, 07u,
+            };",
+        )
+        .unwrap();
+        assert_eq!(s_box.input_bits(), 4);
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0x6, 4))), 0xb);
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0xf, 4))), 0x7);
+
+        let s_box = SBox::from_c_array("const int s[2][2] = {{2, 0}, {3, 1}};").unwrap();
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0x2, 2))), 0x3);
+
+        assert!(SBox::from_c_array("uint8_t s[8] = {0, 1, 2, 3};").is_err());
+        assert!(SBox::from_c_array("uint8_t s[] = {0, 1, 2, 0x3g};").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use crate::block_cipher::xor;
+use crate::reference::{HEYS_S_BOX, PRESENT_S_BOX};
+use crate::{bits2num, num2bits, PBox, SBox, Spn};
+
+const S_DES_P10: [u32; 10] = [3, 5, 2, 7, 4, 10, 1, 9, 8, 6];
+const S_DES_P8: [u32; 8] = [6, 3, 7, 4, 8, 5, 10, 9];
+const S_DES_IP: [u32; 8] = [2, 6, 3, 1, 4, 8, 5, 7];
+const S_DES_EP: [u32; 8] = [4, 1, 2, 3, 2, 3, 4, 1];
+const S_DES_P4: [u32; 4] = [2, 4, 3, 1];
+const S_DES_S_BOXES: [[[u32; 4]; 4]; 2] = [
+    [[1, 0, 3, 2], [3, 2, 1, 0], [0, 2, 1, 3], [3, 1, 3, 2]],
+    [[0, 1, 2, 3], [2, 0, 1, 3], [3, 0, 1, 0], [2, 1, 0, 3]],
+];
+
+fn block_bits(value: u128, width: usize) -> Vec<bool> {
+    (0..width)
+        .map(|i| (value >> (width - 1 - i)) & 1 == 1)
+        .collect()
+}
+
+impl Spn {
+    pub fn present80(key: u128) -> Result<Spn, &'static str> {
+        if key >> 80 != 0 {
+            return Err("key does not fit in 80 bits");
+        }
+
+        let s_box = SBox::from_flat(PRESENT_S_BOX.to_vec(), 4, 4)?;
+        let p_box = PBox::transpose(16, 4)?;
+
+        let mask = (1u128 << 80) - 1;
+        let mut register = key;
+        let mut round_keys = vec![block_bits(register >> 16, 64)];
+        for round in 1..32u128 {
+            register = ((register << 61) | (register >> 19)) & mask;
+            let top = s_box.lookup((register >> 76) as u32) as u128;
+            register = (register & !(0xf << 76)) | (top << 76);
+            register ^= round << 15;
+            round_keys.push(block_bits(register >> 16, 64));
+        }
+
+        Spn::new(s_box, p_box, round_keys)
+    }
+}
+
+pub struct MiniAes {
+    s_box: SBox,
+    shift_row: PBox,
+    round_keys: [u32; 3],
+}
+
+impl MiniAes {
+    pub fn new(key: u32) -> Result<MiniAes, &'static str> {
+        if key >> 16 != 0 {
+            return Err("key does not fit in 16 bits");
+        }
+
+        let s_box = SBox::from_flat(HEYS_S_BOX.to_vec(), 4, 4)?;
+        let shift_row = PBox::new(vec![1, 2, 3, 4, 13, 14, 15, 16, 9, 10, 11, 12, 5, 6, 7, 8])?;
+
+        let mut words: Vec<u32> = (0..4).map(|i| (key >> (12 - 4 * i)) & 0xf).collect();
+        for rcon in [0x1, 0x2] {
+            let n = words.len();
+            words.push(words[n - 4] ^ s_box.lookup(words[n - 1]) ^ rcon);
+            for _ in 0..3 {
+                let n = words.len();
+                words.push(words[n - 4] ^ words[n - 1]);
+            }
+        }
+        let round_keys = [0, 1, 2].map(|i| {
+            words[4 * i..4 * i + 4]
+                .iter()
+                .fold(0, |acc, &word| (acc << 4) | word)
+        });
+
+        Ok(MiniAes {
+            s_box,
+            shift_row,
+            round_keys,
+        })
+    }
+
+    pub fn round_keys(&self) -> &[u32; 3] {
+        &self.round_keys
+    }
+
+    fn gf_mul(mut a: u32, mut b: u32) -> u32 {
+        let mut result = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                result ^= a;
+            }
+            a <<= 1;
+            if a & 0x10 != 0 {
+                a ^= 0x13;
+            }
+            b >>= 1;
+        }
+
+        result
+    }
+
+    fn nibble_sub(&self, state: u32, inverse: bool) -> u32 {
+        (0..4).rev().fold(0, |acc, i| {
+            let nibble = (state >> (4 * i)) & 0xf;
+            let nibble = match inverse {
+                true => self.s_box.lookup_inv(nibble),
+                false => self.s_box.lookup(nibble),
+            };
+            (acc << 4) | nibble
+        })
+    }
+
+    fn mix_column(state: u32) -> u32 {
+        [(state >> 8) & 0xff, state & 0xff]
+            .iter()
+            .fold(0, |acc, &column| {
+                let (a, b) = (column >> 4, column & 0xf);
+                let c = Self::gf_mul(3, a) ^ Self::gf_mul(2, b);
+                let d = Self::gf_mul(2, a) ^ Self::gf_mul(3, b);
+                (acc << 8) | (c << 4) | d
+            })
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        let mut state = bits2num(bits) ^ self.round_keys[0];
+        state = self.shift_row.encrypt_value(self.nibble_sub(state, false));
+        state = Self::mix_column(state) ^ self.round_keys[1];
+        state = self.shift_row.encrypt_value(self.nibble_sub(state, false));
+
+        num2bits(state ^ self.round_keys[2], 16)
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
+        let mut state = bits2num(bits) ^ self.round_keys[2];
+        state = self.nibble_sub(self.shift_row.decrypt_value(state), true);
+        state = Self::mix_column(state ^ self.round_keys[1]);
+        state = self.nibble_sub(self.shift_row.decrypt_value(state), true);
+
+        num2bits(state ^ self.round_keys[0], 16)
+    }
+}
+
+fn gather(table: &[u32]) -> Result<PBox, &'static str> {
+    Ok(PBox::new(table.to_vec())?.pow(-1))
+}
+
+fn select(bits: &[bool], table: &[u32]) -> Vec<bool> {
+    table.iter().map(|&i| bits[i as usize - 1]).collect()
+}
+
+pub struct SDes {
+    initial: PBox,
+    p4: PBox,
+    subkeys: [Vec<bool>; 2],
+}
+
+impl SDes {
+    pub fn new(key: u32) -> Result<SDes, &'static str> {
+        if key >> 10 != 0 {
+            return Err("key does not fit in 10 bits");
+        }
+
+        let shift = PBox::rotate_left(5, 1)?;
+        let rotate = |bits: &[bool]| -> Vec<bool> {
+            bits.chunks(5)
+                .flat_map(|half| shift.encrypt(half))
+                .collect()
+        };
+
+        let first = rotate(&gather(&S_DES_P10)?.encrypt(&num2bits(key, 10)));
+        let second = rotate(&rotate(&first));
+
+        Ok(SDes {
+            initial: gather(&S_DES_IP)?,
+            p4: gather(&S_DES_P4)?,
+            subkeys: [select(&first, &S_DES_P8), select(&second, &S_DES_P8)],
+        })
+    }
+
+    pub fn subkeys(&self) -> &[Vec<bool>; 2] {
+        &self.subkeys
+    }
+
+    fn round_function(&self, bits: &[bool], subkey: &[bool]) -> Vec<bool> {
+        let expanded = xor(&select(bits, &S_DES_EP), subkey);
+        let substituted: Vec<bool> = expanded
+            .chunks(4)
+            .zip(S_DES_S_BOXES)
+            .flat_map(|(chunk, s_box)| {
+                let row = (chunk[0] as usize) << 1 | chunk[3] as usize;
+                let column = (chunk[1] as usize) << 1 | chunk[2] as usize;
+                num2bits(s_box[row][column], 2)
+            })
+            .collect();
+
+        self.p4.encrypt(&substituted)
+    }
+
+    fn feistel(&self, bits: &[bool], subkeys: [&[bool]; 2]) -> Vec<bool> {
+        let state = self.initial.encrypt(bits);
+        let (mut left, mut right) = (state[..4].to_vec(), state[4..].to_vec());
+        for subkey in subkeys {
+            let next = xor(&left, &self.round_function(&right, subkey));
+            (left, right) = (right, next);
+        }
+
+        let state: Vec<bool> = right.into_iter().chain(left).collect();
+        self.initial.decrypt(&state)
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        self.feistel(bits, [&self.subkeys[0], &self.subkeys[1]])
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
+        self.feistel(bits, [&self.subkeys[1], &self.subkeys[0]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present80() {
+        let vectors = [
+            (0x0, 0x0, 0x5579c1387b228445),
+            (0xffff_ffff_ffff_ffff_ffff, 0x0, 0xe72c46c0f5945049),
+            (0x0, 0xffff_ffff_ffff_ffff, 0xa112ffc72f68417b),
+            (
+                0xffff_ffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0x3333dcd3213210d2,
+            ),
+        ];
+        for (key, plaintext, ciphertext) in vectors {
+            let spn = Spn::present80(key).unwrap();
+            assert_eq!(spn.rounds(), 31);
+
+            let encrypted = spn.encrypt(&block_bits(plaintext, 64));
+            assert_eq!(encrypted, block_bits(ciphertext, 64));
+            assert_eq!(spn.decrypt(&encrypted), block_bits(plaintext, 64));
+        }
+
+        assert!(Spn::present80(1 << 80).is_err());
+    }
+
+    #[test]
+    fn test_mini_aes() {
+        let mini_aes = MiniAes::new(0xc3f0).unwrap();
+        assert_eq!(mini_aes.round_keys(), &[0xc3f0, 0x30ff, 0x6696]);
+
+        let plaintext = num2bits(0x9c63, 16);
+        let ciphertext = mini_aes.encrypt(&plaintext);
+        assert_eq!(bits2num(&ciphertext), 0x72c6);
+        assert_eq!(mini_aes.decrypt(&ciphertext), plaintext);
+        assert!(MiniAes::new(0x10000).is_err());
+    }
+
+    #[test]
+    fn test_s_des() {
+        let s_des = SDes::new(0b1010000010).unwrap();
+        assert_eq!(bits2num(&s_des.subkeys()[0]), 0b10100100);
+        assert_eq!(bits2num(&s_des.subkeys()[1]), 0b01000011);
+
+        let plaintext = num2bits(0b10010111, 8);
+        let ciphertext = s_des.encrypt(&plaintext);
+        assert_eq!(bits2num(&ciphertext), 0b00111000);
+        assert_eq!(s_des.decrypt(&ciphertext), plaintext);
+        assert!(SDes::new(1 << 10).is_err());
+    }
+}
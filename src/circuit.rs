@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{ConstantTimeSBox, SBox};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum GateOp {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Gate {
+    pub op: GateOp,
+    pub a: usize,
+    pub b: usize,
+}
+
+pub struct Circuit {
+    pub inputs: usize,
+    pub gates: Vec<Gate>,
+    pub outputs: Vec<usize>,
+}
+
+impl Circuit {
+    pub fn cost(&self) -> usize {
+        self.gates.len()
+    }
+
+    pub fn evaluate(&self, x: u32) -> u32 {
+        let mut signals: Vec<bool> = (0..self.inputs)
+            .map(|i| (x >> (self.inputs - 1 - i)) & 1 == 1)
+            .collect();
+        for gate in &self.gates {
+            let (a, b) = (signals[gate.a], signals[gate.b]);
+            signals.push(match gate.op {
+                GateOp::And => a & b,
+                GateOp::Or => a | b,
+                GateOp::Xor => a ^ b,
+                GateOp::Not => !a,
+            });
+        }
+
+        self.outputs
+            .iter()
+            .fold(0, |acc, &output| (acc << 1) | signals[output] as u32)
+    }
+
+    pub fn to_rust(&self, name: &str) -> String {
+        let signal = |signal: usize| match signal < self.inputs {
+            true => format!("x[{}]", signal),
+            false => format!("t{}", signal - self.inputs),
+        };
+
+        let mut result = format!(
+            "pub fn {}(x: [u64; {}]) -> [u64; {}] {{\n",
+            name.to_lowercase(),
+            self.inputs,
+            self.outputs.len()
+        );
+        for (i, gate) in self.gates.iter().enumerate() {
+            let (a, b) = (signal(gate.a), signal(gate.b));
+            let expression = match gate.op {
+                GateOp::And => format!("{} & {}", a, b),
+                GateOp::Or => format!("{} | {}", a, b),
+                GateOp::Xor => format!("{} ^ {}", a, b),
+                GateOp::Not => format!("!{}", a),
+            };
+            result.push_str(&format!("    let t{} = {};\n", i, expression));
+        }
+        let outputs: Vec<String> = self.outputs.iter().map(|&output| signal(output)).collect();
+        result.push_str(&format!("    [{}]\n}}\n", outputs.join(", ")));
+
+        result
+    }
+}
+
+impl fmt::Display for Circuit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |signal: usize| match signal < self.inputs {
+            true => format!("x{}", signal),
+            false => format!("t{}", signal - self.inputs),
+        };
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            let (a, b) = (name(gate.a), name(gate.b));
+            match gate.op {
+                GateOp::And => writeln!(f, "t{} = {} & {}", i, a, b)?,
+                GateOp::Or => writeln!(f, "t{} = {} | {}", i, a, b)?,
+                GateOp::Xor => writeln!(f, "t{} = {} ^ {}", i, a, b)?,
+                GateOp::Not => writeln!(f, "t{} = ~{}", i, a)?,
+            }
+        }
+        for (j, &output) in self.outputs.iter().enumerate() {
+            writeln!(f, "y{} = {}", j, name(output))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct CircuitSearch {
+    full: u32,
+    targets: Vec<u32>,
+    signals: Vec<u32>,
+    gates: Vec<Gate>,
+}
+
+impl CircuitSearch {
+    fn missing(&self) -> usize {
+        self.targets
+            .iter()
+            .filter(|target| !self.signals.contains(target))
+            .count()
+    }
+
+    fn search(&mut self, budget: usize) -> bool {
+        let missing = self.missing();
+        if missing == 0 {
+            return true;
+        }
+        if missing > budget {
+            return false;
+        }
+
+        let last = self.signals.len() - 1;
+        let previous = self.gates.last().copied();
+        for a in 0..self.signals.len() {
+            for b in a..self.signals.len() {
+                for op in [GateOp::And, GateOp::Or, GateOp::Xor, GateOp::Not] {
+                    if (op == GateOp::Not) != (a == b) {
+                        continue;
+                    }
+
+                    let gate = Gate { op, a, b };
+                    let independent = previous.is_some() && a != last && b != last;
+                    if independent && Some(gate) <= previous {
+                        continue;
+                    }
+
+                    let (x, y) = (self.signals[a], self.signals[b]);
+                    let value = match op {
+                        GateOp::And => x & y,
+                        GateOp::Or => x | y,
+                        GateOp::Xor => x ^ y,
+                        GateOp::Not => !x & self.full,
+                    };
+                    if value == 0 || value == self.full || self.signals.contains(&value) {
+                        continue;
+                    }
+
+                    self.signals.push(value);
+                    self.gates.push(gate);
+                    if self.search(budget - 1) {
+                        return true;
+                    }
+                    self.signals.pop();
+                    self.gates.pop();
+                }
+            }
+        }
+
+        false
+    }
+}
+
+struct CircuitBuilder {
+    inputs: usize,
+    gates: Vec<Gate>,
+    monomials: HashMap<u32, usize>,
+}
+
+impl CircuitBuilder {
+    fn push(&mut self, op: GateOp, a: usize, b: usize) -> usize {
+        self.gates.push(Gate { op, a, b });
+        self.inputs + self.gates.len() - 1
+    }
+
+    fn monomial(&mut self, monomial: u32) -> usize {
+        if let Some(&signal) = self.monomials.get(&monomial) {
+            return signal;
+        }
+
+        let low = monomial.trailing_zeros() as usize;
+        let input = self.inputs - 1 - low;
+        let rest = monomial & (monomial - 1);
+        let signal = match rest {
+            0 => input,
+            _ => {
+                let rest = self.monomial(rest);
+                self.push(GateOp::And, rest, input)
+            }
+        };
+        self.monomials.insert(monomial, signal);
+
+        signal
+    }
+}
+
+impl SBox {
+    pub fn anf_circuit(&self) -> Circuit {
+        let anf = ConstantTimeSBox::moebius(&self.table);
+        let mut builder = CircuitBuilder {
+            inputs: self.input_bits,
+            gates: Vec::new(),
+            monomials: HashMap::new(),
+        };
+
+        let outputs = (0..self.output_bits)
+            .map(|j| {
+                let bit = 1 << (self.output_bits - 1 - j);
+                let mut signal = None;
+                for (monomial, _) in anf
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .filter(|&(_, &c)| c & bit != 0)
+                {
+                    let term = builder.monomial(monomial as u32);
+                    signal = Some(match signal {
+                        None => term,
+                        Some(acc) => builder.push(GateOp::Xor, acc, term),
+                    });
+                }
+
+                let signal = signal.unwrap_or_else(|| builder.push(GateOp::Xor, 0, 0));
+                match anf[0] & bit {
+                    0 => signal,
+                    _ => builder.push(GateOp::Not, signal, signal),
+                }
+            })
+            .collect();
+
+        Circuit {
+            inputs: self.input_bits,
+            gates: builder.gates,
+            outputs,
+        }
+    }
+
+    pub fn find_circuit(&self, max_gates: usize) -> Option<Circuit> {
+        if self.input_bits > 5 {
+            return None;
+        }
+
+        let size = 1usize << self.input_bits;
+        let truth_table =
+            |f: &dyn Fn(usize) -> bool| (0..size).fold(0u32, |acc, x| acc | (f(x) as u32) << x);
+
+        let inputs: Vec<u32> = (0..self.input_bits)
+            .map(|i| truth_table(&|x| (x >> (self.input_bits - 1 - i)) & 1 == 1))
+            .collect();
+        let targets: Vec<u32> = (0..self.output_bits)
+            .map(|j| truth_table(&|x| (self.table[x] >> (self.output_bits - 1 - j)) & 1 == 1))
+            .collect();
+
+        let mut search = CircuitSearch {
+            full: (u64::MAX >> (64 - size)) as u32,
+            targets,
+            signals: inputs,
+            gates: Vec::new(),
+        };
+        let found = (0..=max_gates).any(|budget| {
+            search.signals.truncate(self.input_bits);
+            search.gates.clear();
+            search.search(budget)
+        });
+        if !found {
+            return None;
+        }
+
+        Some(Circuit {
+            inputs: self.input_bits,
+            outputs: search
+                .targets
+                .iter()
+                .map(|target| search.signals.iter().position(|s| s == target).unwrap())
+                .collect(),
+            gates: search.gates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+
+    #[test]
+    fn test_find_circuit() {
+        let table: Vec<u32> = (0..8u32)
+            .map(|x| {
+                let (a, b, c) = (x >> 2, (x >> 1) & 1, x & 1);
+                ((a ^ b) << 2) | ((b | c) << 1) | (a & !c & 1)
+            })
+            .collect();
+        let s_box = SBox::new(vec![table.clone()]).unwrap();
+
+        let circuit = s_box.find_circuit(6).unwrap();
+        assert_eq!(circuit.cost(), 4);
+        for (x, &y) in table.iter().enumerate() {
+            assert_eq!(circuit.evaluate(x as u32), y);
+        }
+        assert!(s_box.find_circuit(3).is_none());
+        assert_eq!(
+            circuit.to_string(),
+            "t0 = x0 ^ x1\nt1 = x0 ^ x2\nt2 = x0 & t1\nt3 = x1 | x2\ny0 = t0\ny1 = t3\ny2 = t2\n"
+        );
+    }
+
+    #[test]
+    fn test_anf_circuit() {
+        let s_box = heys_s_box();
+
+        let circuit = s_box.anf_circuit();
+        for (x, &y) in s_box.table.iter().enumerate() {
+            assert_eq!(circuit.evaluate(x as u32), y);
+        }
+
+        let code = circuit.to_rust("heys");
+        assert!(code.starts_with("pub fn heys(x: [u64; 4]) -> [u64; 4] {\n    let t0 = "));
+        assert!(code.ends_with("]\n}\n"));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{SBox, Spn};
+
+pub struct Cnf {
+    pub variable_count: usize,
+    pub states: Vec<Vec<i32>>,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    pub fn to_dimacs(&self) -> String {
+        let mut result = String::new();
+        for (round, state) in self.states.iter().enumerate() {
+            let variables: Vec<String> = state.iter().map(i32::to_string).collect();
+            result.push_str(&format!("c x{} {}\n", round, variables.join(" ")));
+        }
+        result.push_str(&format!(
+            "p cnf {} {}\n",
+            self.variable_count,
+            self.clauses.len()
+        ));
+        for clause in &self.clauses {
+            for literal in clause {
+                result.push_str(&format!("{} ", literal));
+            }
+            result.push_str("0\n");
+        }
+
+        result
+    }
+}
+
+impl SBox {
+    pub fn ddt_cnf(&self, input: &[i32], output: &[i32]) -> Result<Vec<Vec<i32>>, &'static str> {
+        if input.len() != self.input_bits || output.len() != self.output_bits {
+            return Err("variable count does not match s-box width");
+        }
+
+        Ok(self.ddt_clauses(input, output))
+    }
+
+    fn ddt_clauses(&self, input: &[i32], output: &[i32]) -> Vec<Vec<i32>> {
+        let literal = |variables: &[i32], value: u32| -> Vec<i32> {
+            let n = variables.len();
+            variables
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| {
+                    if (value >> (n - 1 - i)) & 1 == 1 {
+                        -v
+                    } else {
+                        v
+                    }
+                })
+                .collect()
+        };
+
+        let mut clauses = Vec::new();
+        for (a, row) in self.ddt_rows().enumerate() {
+            for (b, &count) in row.iter().enumerate() {
+                if count == 0 {
+                    let mut clause = literal(input, a as u32);
+                    clause.extend(literal(output, b as u32));
+                    clauses.push(clause);
+                }
+            }
+        }
+
+        clauses
+    }
+}
+
+impl Spn {
+    pub fn to_cnf(&self, rounds: usize) -> Cnf {
+        let n = self.block_size();
+        let s_box_size = self.s_box().input_bits();
+
+        let states: Vec<Vec<i32>> = (0..=rounds)
+            .map(|round| (0..n).map(|i| (round * n + i + 1) as i32).collect())
+            .collect();
+        let mut clauses = vec![states[0].clone()];
+        for round in 0..rounds {
+            let output: Vec<i32> = self
+                .p_box()
+                .permutation
+                .iter()
+                .map(|&num| states[round + 1][num as usize - 1])
+                .collect();
+
+            for j in 0..self.s_box_count() {
+                let range = j * s_box_size..(j + 1) * s_box_size;
+                clauses.extend(
+                    self.s_box()
+                        .ddt_clauses(&states[round][range.clone()], &output[range]),
+                );
+            }
+        }
+
+        Cnf {
+            variable_count: (rounds + 1) * n,
+            states,
+            clauses,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{num2bits, PBox, SBox, Spn};
+
+    #[test]
+    fn test_ddt_cnf() {
+        let s_box = SBox::new(vec![vec![0, 3], vec![1, 2]]).unwrap();
+        let ddt = s_box.ddt();
+        let clauses = s_box.ddt_cnf(&[1, 2], &[3, 4]).unwrap();
+
+        for (a, row) in ddt.iter().enumerate() {
+            for (b, &count) in row.iter().enumerate() {
+                let value = |v: i32| ((a << 2 | b) >> (4 - v)) & 1 == 1;
+                let satisfied = clauses
+                    .iter()
+                    .all(|clause| clause.iter().any(|&l| value(l.abs()) == (l > 0)));
+                assert_eq!(satisfied, count != 0);
+            }
+        }
+
+        assert_eq!(
+            s_box.ddt_cnf(&[1], &[3, 4]),
+            Err("variable count does not match s-box width")
+        );
+        assert!(s_box.ddt_cnf(&[1, 2], &[3, 4, 5]).is_err());
+    }
+
+    #[test]
+    fn test_spn_cnf() {
+        let s_box = SBox::new(vec![vec![0, 3], vec![1, 2]]).unwrap();
+        let p_box = PBox::new(vec![3, 4, 1, 2]).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 4); 3]).unwrap();
+
+        let cnf = spn.to_cnf(2);
+        assert_eq!(cnf.variable_count, 12);
+        assert_eq!(cnf.states[1], vec![5, 6, 7, 8]);
+
+        let dimacs = cnf.to_dimacs();
+        assert!(dimacs.starts_with("c x0 1 2 3 4\n"));
+        assert!(dimacs.contains(&format!("p cnf 12 {}\n1 2 3 4 0\n", cnf.clauses.len())));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{bits2num, num2bits, BlockCipher, SBox};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+const MAX_CODEBOOK_BITS: usize = 24;
+
+fn check_block_size(n: usize) -> Result<(), &'static str> {
+    if n == 0 || n > MAX_CODEBOOK_BITS {
+        return Err("block size is too large for a codebook");
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn codebook<C: BlockCipher + Sync>(cipher: &C) -> Result<SBox, &'static str> {
+    let n = cipher.block_size();
+    check_block_size(n)?;
+
+    let table = (0..1u32 << n)
+        .map(|x| bits2num(&cipher.encrypt_block(&num2bits(x, n))))
+        .collect();
+
+    SBox::from_flat(table, n, n)
+}
+
+#[cfg(feature = "parallel")]
+pub fn codebook<C: BlockCipher + Sync>(cipher: &C) -> Result<SBox, &'static str> {
+    let n = cipher.block_size();
+    check_block_size(n)?;
+
+    let table = (0..1u32 << n)
+        .into_par_iter()
+        .map(|x| bits2num(&cipher.encrypt_block(&num2bits(x, n))))
+        .collect();
+
+    SBox::from_flat(table, n, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MiniAes, SDes, Spn};
+
+    #[test]
+    fn test_codebook() {
+        let s_des = SDes::new(0b1010000010).unwrap();
+        let table = codebook(&s_des).unwrap();
+        assert_eq!(table.lookup(0b10010111), 0b00111000);
+        assert!(table.verify().is_empty());
+
+        let mini_aes = MiniAes::new(0xc3f0).unwrap();
+        assert_eq!(codebook(&mini_aes).unwrap().lookup(0x9c63), 0x72c6);
+
+        assert!(codebook(&Spn::present80(0).unwrap()).is_err());
+    }
+}
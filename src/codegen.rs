@@ -0,0 +1,198 @@
+use crate::{PBox, SBox};
+
+fn array_body(values: &[u32], hex_digits: usize) -> String {
+    let mut result = String::new();
+    for line in values.chunks(16) {
+        let line = line
+            .iter()
+            .map(|&value| match hex_digits {
+                0 => value.to_string(),
+                _ => format!("0x{:0width$x}", value, width = hex_digits),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.push_str(&format!("    {},\n", line));
+    }
+
+    result
+}
+
+fn rust_array(name: &str, values: &[u32], hex_digits: usize) -> String {
+    let mut result = format!("pub const {}: [u32; {}] = [\n", name, values.len());
+    result.push_str(&array_body(values, hex_digits));
+    result.push_str("];\n");
+
+    result
+}
+
+fn c_type(bit_count: usize) -> &'static str {
+    match bit_count {
+        0..=8 => "uint8_t",
+        9..=16 => "uint16_t",
+        _ => "uint32_t",
+    }
+}
+
+fn c_array(name: &str, c_type: &str, values: &[u32], hex_digits: usize) -> String {
+    let mut result = format!("static const {} {}[{}] = {{\n", c_type, name, values.len());
+    result.push_str(&array_body(values, hex_digits));
+    result.push_str("};\n");
+
+    result
+}
+
+pub struct CHeader {
+    name: String,
+    items: Vec<String>,
+}
+
+impl CHeader {
+    pub fn new(name: &str) -> CHeader {
+        CHeader {
+            name: name.to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn add_s_box(&mut self, name: &str, s_box: &SBox, with_inverse: bool) -> &mut CHeader {
+        let name = name.to_lowercase();
+        let macro_name = name.to_uppercase();
+
+        let mut item = format!(
+            "#define {}_INPUT_BITS {}\n#define {}_OUTPUT_BITS {}\n\n",
+            macro_name, s_box.input_bits, macro_name, s_box.output_bits
+        );
+        item.push_str(&c_array(
+            &name,
+            c_type(s_box.output_bits),
+            &s_box.table,
+            s_box.output_bits.div_ceil(4),
+        ));
+        if with_inverse {
+            item.push('\n');
+            item.push_str(&c_array(
+                &format!("{}_inverse", name),
+                c_type(s_box.input_bits),
+                s_box.inverse_table(),
+                s_box.input_bits.div_ceil(4),
+            ));
+        }
+
+        self.items.push(item);
+        self
+    }
+
+    pub fn add_p_box(&mut self, name: &str, p_box: &PBox) -> &mut CHeader {
+        let name = name.to_lowercase();
+        let positions: Vec<u32> = p_box.permutation.iter().map(|&num| num - 1).collect();
+        let position_bits =
+            (u32::BITS - (p_box.len().saturating_sub(1) as u32).leading_zeros()) as usize;
+
+        let mut item = format!(
+            "#define {}_BITS {}\n\n",
+            name.to_uppercase(),
+            positions.len()
+        );
+        item.push_str("/* output position of each input bit, bit 0 is the most significant */\n");
+        item.push_str(&c_array(&name, c_type(position_bits), &positions, 0));
+
+        self.items.push(item);
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let guard = format!("{}_H", self.name.to_uppercase());
+
+        let mut result = format!(
+            "#ifndef {}\n#define {}\n\n#include <stdint.h>\n",
+            guard, guard
+        );
+        for item in &self.items {
+            result.push('\n');
+            result.push_str(item);
+        }
+        result.push_str(&format!("\n#endif /* {} */\n", guard));
+
+        result
+    }
+}
+
+impl SBox {
+    pub fn to_rust(&self, name: &str) -> String {
+        let constant = name.to_uppercase();
+
+        rust_array(&constant, &self.table, self.output_bits.div_ceil(4))
+            + &format!(
+                "\npub fn {}() -> ps_blocks::SBox {{\n    ps_blocks::SBox::new(vec![{}.to_vec()]).unwrap()\n}}\n",
+                name.to_lowercase(),
+                constant
+            )
+    }
+
+    pub fn to_rust_bitsliced(&self, name: &str) -> String {
+        self.anf_circuit().to_rust(name)
+    }
+}
+
+impl PBox {
+    pub fn to_rust(&self, name: &str) -> String {
+        let constant = name.to_uppercase();
+
+        rust_array(&constant, &self.permutation, 0)
+            + &format!(
+                "\npub fn {}() -> ps_blocks::PBox {{\n    ps_blocks::PBox::new({}.to_vec()).unwrap()\n}}\n",
+                name.to_lowercase(),
+                constant
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_rust() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        assert_eq!(
+            s_box.to_rust("small_s_box"),
+            "pub const SMALL_S_BOX: [u32; 4] = [\n    0x2, 0x0, 0x3, 0x1,\n];\n\n\
+             pub fn small_s_box() -> ps_blocks::SBox {\n    \
+             ps_blocks::SBox::new(vec![SMALL_S_BOX.to_vec()]).unwrap()\n}\n"
+        );
+
+        let p_box = PBox::new(vec![2, 3, 1]).unwrap();
+        assert_eq!(
+            p_box.to_rust("ROTATION"),
+            "pub const ROTATION: [u32; 3] = [\n    2, 3, 1,\n];\n\n\
+             pub fn rotation() -> ps_blocks::PBox {\n    \
+             ps_blocks::PBox::new(ROTATION.to_vec()).unwrap()\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_c_header() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let p_box = PBox::new(vec![2, 3, 1]).unwrap();
+
+        let header = CHeader::new("boxes")
+            .add_s_box("s", &s_box, true)
+            .add_p_box("p", &p_box)
+            .render();
+        assert_eq!(
+            header,
+            "#ifndef BOXES_H\n#define BOXES_H\n\n#include <stdint.h>\n\n\
+             #define S_INPUT_BITS 2\n#define S_OUTPUT_BITS 2\n\n\
+             static const uint8_t s[4] = {\n    0x2, 0x0, 0x3, 0x1,\n};\n\n\
+             static const uint8_t s_inverse[4] = {\n    0x1, 0x3, 0x0, 0x2,\n};\n\n\
+             #define P_BITS 3\n\n\
+             /* output position of each input bit, bit 0 is the most significant */\n\
+             static const uint8_t p[3] = {\n    1, 2, 0,\n};\n\n\
+             #endif /* BOXES_H */\n"
+        );
+
+        let wide = PBox::new((1..=300).rev().collect()).unwrap();
+        let header = CHeader::new("wide").add_p_box("p", &wide).render();
+        assert!(header.contains("static const uint16_t p[300] = {\n    299, 298,"));
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::Read;
+
+use serde::Deserialize;
+
+use crate::{BitOrder, PBox, PBoxBuilder, SBox, SBoxBuilder};
+
+pub enum FileFormat {
+    Json,
+    Toml,
+}
+
+#[derive(Deserialize)]
+struct SBoxFile {
+    input_bits: usize,
+    output_bits: usize,
+    #[serde(default)]
+    bit_order: BitOrder,
+    values: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct PBoxFile {
+    #[serde(default)]
+    bit_order: BitOrder,
+    permutation: Vec<u32>,
+}
+
+fn parse<T: for<'de> Deserialize<'de>, R: Read>(
+    mut reader: R,
+    format: FileFormat,
+) -> Result<T, &'static str> {
+    let mut text = String::new();
+    reader
+        .read_to_string(&mut text)
+        .map_err(|_| "failed to read input")?;
+
+    match format {
+        FileFormat::Json => serde_json::from_str(&text).map_err(|_| "invalid json"),
+        FileFormat::Toml => toml::from_str(&text).map_err(|_| "invalid toml"),
+    }
+}
+
+impl SBox {
+    pub fn from_reader<R: Read>(reader: R, format: FileFormat) -> Result<SBox, &'static str> {
+        let file: SBoxFile = parse(reader, format)?;
+
+        SBoxBuilder::new()
+            .input_bits(file.input_bits)
+            .output_bits(file.output_bits)
+            .bit_order(file.bit_order)
+            .values(file.values)
+            .build()
+    }
+}
+
+impl PBox {
+    pub fn from_reader<R: Read>(reader: R, format: FileFormat) -> Result<PBox, &'static str> {
+        let file: PBoxFile = parse(reader, format)?;
+
+        PBoxBuilder::new()
+            .bit_order(file.bit_order)
+            .permutation(file.permutation)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_from_reader() {
+        let json = r#"{
+            "input_bits": 4,
+            "output_bits": 4,
+            "values": [14, 4, 13, 1, 2, 15, 11, 8, 3, 10, 6, 12, 5, 9, 0, 7]
+        }"#;
+        let s_box = SBox::from_reader(json.as_bytes(), FileFormat::Json).unwrap();
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0x6, 4))), 0xb);
+
+        let toml = "
+            input_bits = 2
+            output_bits = 2
+            bit_order = \"lsb-first\"
+            values = [1, 2, 3, 0]
+        ";
+        let s_box = SBox::from_reader(toml.as_bytes(), FileFormat::Toml).unwrap();
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0b10, 2))), 0b01);
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0b01, 2))), 0b11);
+
+        let toml = "
+            bit_order = \"lsb-first\"
+            permutation = [2, 3, 4, 1]
+        ";
+        let p_box = PBox::from_reader(toml.as_bytes(), FileFormat::Toml).unwrap();
+        assert_eq!(
+            p_box.encrypt(&[false, false, false, true]),
+            [false, false, true, false]
+        );
+
+        assert!(SBox::from_reader("{}".as_bytes(), FileFormat::Json).is_err());
+        assert!(PBox::from_reader("permutation = [1, 1]".as_bytes(), FileFormat::Toml).is_err());
+    }
+}
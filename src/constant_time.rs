@@ -0,0 +1,74 @@
+use crate::{bits2num, num2bits, SBox};
+
+pub struct ConstantTimeSBox {
+    anf: Vec<u32>,
+    inverse_anf: Vec<u32>,
+    input_bits: usize,
+    output_bits: usize,
+}
+
+impl ConstantTimeSBox {
+    pub fn new(s_box: &SBox) -> ConstantTimeSBox {
+        ConstantTimeSBox {
+            anf: Self::moebius(&s_box.table),
+            inverse_anf: Self::moebius(s_box.inverse_table()),
+            input_bits: s_box.input_bits,
+            output_bits: s_box.output_bits,
+        }
+    }
+
+    pub(crate) fn moebius(table: &[u32]) -> Vec<u32> {
+        let mut anf = table.to_vec();
+        let mut step = 1;
+        while step < anf.len() {
+            for x in 0..anf.len() {
+                if x & step != 0 {
+                    anf[x] ^= anf[x ^ step];
+                }
+            }
+            step <<= 1;
+        }
+
+        anf
+    }
+
+    fn evaluate(anf: &[u32], x: u32) -> u32 {
+        let mut result = 0;
+        for (monomial, &coefficients) in anf.iter().enumerate() {
+            let missing = !x & monomial as u32;
+            let mask = 0u32.wrapping_sub(((missing as u64).wrapping_sub(1) >> 63) as u32);
+            result ^= coefficients & mask;
+        }
+
+        result
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(Self::evaluate(&self.anf, bits2num(bits)), self.output_bits)
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(
+            Self::evaluate(&self.inverse_anf, bits2num(bits)),
+            self.input_bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+
+    #[test]
+    fn test_constant_time() {
+        let s_box = heys_s_box();
+        let constant_time = ConstantTimeSBox::new(&s_box);
+
+        for a in 0..16 {
+            let a_bits = num2bits(a, 4);
+            assert_eq!(constant_time.encrypt(&a_bits), s_box.encrypt(&a_bits));
+            assert_eq!(constant_time.decrypt(&a_bits), s_box.decrypt(&a_bits));
+        }
+    }
+}
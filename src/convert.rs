@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use crate::{PBox, SBox};
+
+fn parse_values(text: &str) -> Result<Vec<u32>, &'static str> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix(['[', '{', '('])
+        .and_then(|inner| inner.strip_suffix([']', '}', ')']))
+        .unwrap_or(text);
+
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            match value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+            {
+                Some(digits) => u32::from_str_radix(digits, 16),
+                None => value.parse(),
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|_| "invalid number in list")
+}
+
+impl TryFrom<Vec<Vec<u32>>> for SBox {
+    type Error = &'static str;
+
+    fn try_from(table: Vec<Vec<u32>>) -> Result<SBox, &'static str> {
+        SBox::new(table)
+    }
+}
+
+impl<const M: usize, const N: usize> TryFrom<[[u32; M]; N]> for SBox {
+    type Error = &'static str;
+
+    fn try_from(table: [[u32; M]; N]) -> Result<SBox, &'static str> {
+        SBox::new(table.iter().map(|row| row.to_vec()).collect())
+    }
+}
+
+impl TryFrom<Vec<u32>> for PBox {
+    type Error = &'static str;
+
+    fn try_from(permutation: Vec<u32>) -> Result<PBox, &'static str> {
+        PBox::new(permutation)
+    }
+}
+
+impl<const N: usize> TryFrom<[u32; N]> for PBox {
+    type Error = &'static str;
+
+    fn try_from(permutation: [u32; N]) -> Result<PBox, &'static str> {
+        PBox::new(permutation.to_vec())
+    }
+}
+
+impl FromStr for SBox {
+    type Err = &'static str;
+
+    fn from_str(text: &str) -> Result<SBox, &'static str> {
+        SBox::new(vec![parse_values(text)?])
+    }
+}
+
+impl FromStr for PBox {
+    type Err = &'static str;
+
+    fn from_str(text: &str) -> Result<PBox, &'static str> {
+        PBox::new(parse_values(text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build() -> Result<(SBox, PBox), &'static str> {
+        let s_box = SBox::try_from([[2, 0], [3, 1]])?;
+        let p_box: PBox = vec![2, 1].try_into()?;
+
+        Ok((s_box, p_box))
+    }
+
+    #[test]
+    fn test_try_from() {
+        let (s_box, p_box) = build().unwrap();
+        assert!(s_box == SBox::try_from(vec![vec![2, 0], vec![3, 1]]).unwrap());
+        assert!(p_box == PBox::try_from([2, 1]).unwrap());
+
+        assert!(SBox::try_from([[0, 1, 2]]).is_err());
+        assert!(PBox::try_from([1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let s_box: SBox = "2, 0, 3, 1".parse().unwrap();
+        assert!(s_box == SBox::try_from([[2, 0], [3, 1]]).unwrap());
+        assert!("[0x2 0x0 0x3 0x1]".parse::<SBox>().unwrap() == s_box);
+
+        let p_box: PBox = "1 5 9 13\n2 6 10 14\n3 7 11 15\n4 8 12 16".parse().unwrap();
+        assert!(p_box == PBox::transpose(4, 4).unwrap());
+        assert!("{2, 1}".parse::<PBox>().unwrap() == PBox::try_from([2, 1]).unwrap());
+        assert!("1, 1".parse::<PBox>().is_err());
+        assert!("1, x".parse::<PBox>().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{PBox, SBox};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Radix {
+    Decimal,
+    Hexadecimal,
+}
+
+pub struct CsvOptions {
+    pub radix: Radix,
+    pub delimiter: char,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            radix: Radix::Decimal,
+            delimiter: ',',
+        }
+    }
+}
+
+impl CsvOptions {
+    fn format_value(&self, value: u32, bit_count: usize) -> String {
+        match self.radix {
+            Radix::Decimal => value.to_string(),
+            Radix::Hexadecimal => format!("{:0width$x}", value, width = bit_count.div_ceil(4)),
+        }
+    }
+
+    fn format_row(&self, values: &[u32], bit_count: usize) -> String {
+        values
+            .iter()
+            .map(|&value| self.format_value(value, bit_count))
+            .collect::<Vec<_>>()
+            .join(&self.delimiter.to_string())
+    }
+
+    fn parse_row(&self, line: &str) -> Result<Vec<u32>, &'static str> {
+        line.split(self.delimiter)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| match self.radix {
+                Radix::Decimal => value.parse().map_err(|_| "invalid decimal value"),
+                Radix::Hexadecimal => {
+                    let digits = value
+                        .strip_prefix("0x")
+                        .or_else(|| value.strip_prefix("0X"))
+                        .unwrap_or(value);
+                    u32::from_str_radix(digits, 16).map_err(|_| "invalid hexadecimal value")
+                }
+            })
+            .collect()
+    }
+}
+
+impl SBox {
+    pub fn to_csv(&self, options: &CsvOptions) -> String {
+        let columns = 1 << (self.input_bits - self.input_bits / 2);
+
+        let mut result = String::new();
+        for row in self.table.chunks(columns) {
+            result.push_str(&options.format_row(row, self.output_bits));
+            result.push('\n');
+        }
+
+        result
+    }
+
+    pub fn from_csv(text: &str, options: &CsvOptions) -> Result<SBox, &'static str> {
+        let rows = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| options.parse_row(line))
+            .collect::<Result<Vec<_>, _>>()?;
+        if rows.is_empty() {
+            return Err("invalid table");
+        }
+
+        SBox::new(rows)
+    }
+}
+
+impl PBox {
+    pub fn to_csv(&self, options: &CsvOptions) -> String {
+        let bit_count = 32 - (self.permutation.len() as u32).leading_zeros() as usize;
+
+        options.format_row(&self.permutation, bit_count) + "\n"
+    }
+
+    pub fn from_csv(text: &str, options: &CsvOptions) -> Result<PBox, &'static str> {
+        let mut permutation = Vec::new();
+        for line in text.lines() {
+            permutation.extend(options.parse_row(line)?);
+        }
+
+        PBox::new(permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_csv() {
+        let s_box = heys_s_box();
+
+        let options = CsvOptions {
+            radix: Radix::Hexadecimal,
+            delimiter: ';',
+        };
+        let csv = s_box.to_csv(&options);
+        assert_eq!(csv, "e;4;d;1\n2;f;b;8\n3;a;6;c\n5;9;0;7\n");
+
+        let restored = SBox::from_csv(&csv, &options).unwrap();
+        for a in 0..16 {
+            assert_eq!(
+                bits2num(&restored.encrypt(&num2bits(a, 4))),
+                bits2num(&s_box.encrypt(&num2bits(a, 4)))
+            );
+        }
+        assert!(SBox::from_csv("0x1, 0x0\n0x3, 0x2", &options).is_err());
+        assert!(SBox::from_csv("1, 0, 3, 2", &CsvOptions::default()).is_ok());
+
+        let p_box = PBox::new(vec![4, 2, 7, 1, 3, 8, 5, 6]).unwrap();
+        let csv = p_box.to_csv(&CsvOptions::default());
+        assert_eq!(csv, "4,2,7,1,3,8,5,6\n");
+        assert!(PBox::from_csv(&csv, &CsvOptions::default()).is_ok());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::BlockCipher;
+
+pub struct CtrKeystream<C: BlockCipher> {
+    cipher: C,
+    counter: Vec<bool>,
+    buffer: Vec<bool>,
+}
+
+impl<C: BlockCipher> CtrKeystream<C> {
+    pub fn new(cipher: C, nonce: &[bool]) -> Result<CtrKeystream<C>, &'static str> {
+        if cipher.block_size() == 0 {
+            return Err("block size must be positive");
+        }
+        if nonce.len() != cipher.block_size() {
+            return Err("nonce length does not match block size");
+        }
+
+        Ok(CtrKeystream {
+            cipher,
+            counter: nonce.to_vec(),
+            buffer: Vec::new(),
+        })
+    }
+
+    fn refill(&mut self) {
+        let block = self.cipher.encrypt_block(&self.counter);
+        self.buffer.extend(block);
+
+        for bit in self.counter.iter_mut().rev() {
+            *bit = !*bit;
+            if *bit {
+                break;
+            }
+        }
+    }
+
+    pub fn apply(&mut self, data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .zip(self)
+            .map(|(&byte, key)| byte ^ key)
+            .collect()
+    }
+}
+
+impl<C: BlockCipher> Iterator for CtrKeystream<C> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while self.buffer.len() < 8 {
+            self.refill();
+        }
+
+        let byte = self.buffer[..8]
+            .iter()
+            .fold(0, |acc, &bit| (acc << 1) | bit as u8);
+        self.buffer.drain(..8);
+
+        Some(byte)
+    }
+}
+
+pub fn ctr_encrypt(
+    cipher: impl BlockCipher,
+    nonce: &[bool],
+    data: &[u8],
+) -> Result<Vec<u8>, &'static str> {
+    Ok(CtrKeystream::new(cipher, nonce)?.apply(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits, MiniAes, SDes};
+
+    #[test]
+    fn test_keystream() {
+        let cipher = MiniAes::new(0xc3f0).unwrap();
+        let keystream: Vec<u8> = CtrKeystream::new(&cipher, &num2bits(0xfffe, 16))
+            .unwrap()
+            .take(6)
+            .collect();
+
+        let expected: Vec<u8> = [0xfffe, 0xffff, 0x0000]
+            .iter()
+            .flat_map(|&counter| {
+                let block = bits2num(&cipher.encrypt(&num2bits(counter, 16)));
+                [(block >> 8) as u8, block as u8]
+            })
+            .collect();
+        assert_eq!(keystream, expected);
+    }
+
+    #[test]
+    fn test_ctr_encrypt() {
+        let cipher = SDes::new(0b1010000010).unwrap();
+        let nonce = num2bits(0x42, 8);
+        let message = b"attack at dawn";
+
+        let encrypted = ctr_encrypt(&cipher, &nonce, message).unwrap();
+        assert_ne!(&encrypted[..], message);
+        assert_eq!(ctr_encrypt(&cipher, &nonce, &encrypted).unwrap(), message);
+        assert!(ctr_encrypt(&cipher, &num2bits(0, 16), message).is_err());
+    }
+
+    struct Empty;
+
+    impl BlockCipher for Empty {
+        fn block_size(&self) -> usize {
+            0
+        }
+
+        fn encrypt_block(&self, _: &[bool]) -> Vec<bool> {
+            Vec::new()
+        }
+
+        fn decrypt_block(&self, _: &[bool]) -> Vec<bool> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_empty_block_is_rejected() {
+        assert!(CtrKeystream::new(Empty, &[]).is_err());
+        assert!(ctr_encrypt(Empty, &[], b"a").is_err());
+    }
+}
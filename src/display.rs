@@ -0,0 +1,100 @@
+use std::fmt;
+
+use crate::{PBox, SBox};
+
+fn grid(f: &mut fmt::Formatter, values: &[u32], columns: usize, hex: bool) -> fmt::Result {
+    let format = |value: usize| match hex {
+        true => format!("{:x}", value),
+        false => value.to_string(),
+    };
+
+    let label_width = format(values.len().saturating_sub(columns)).len();
+    let width = values
+        .iter()
+        .map(|&value| format(value as usize).len())
+        .chain([format(columns - 1).len()])
+        .max()
+        .unwrap_or(1);
+
+    write!(f, "{:label_width$} |", "")?;
+    for j in 0..columns {
+        write!(f, " {:>width$}", format(j))?;
+    }
+    writeln!(f)?;
+    writeln!(
+        f,
+        "{}+{}",
+        "-".repeat(label_width + 1),
+        "-".repeat(columns * (width + 1))
+    )?;
+
+    for (i, row) in values.chunks(columns).enumerate() {
+        write!(f, "{:>label_width$} |", format(i * columns))?;
+        for &value in row {
+            write!(f, " {:>width$}", format(value as usize))?;
+        }
+        writeln!(f)?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for SBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        grid(
+            f,
+            &self.table,
+            1 << (self.input_bits - self.input_bits / 2),
+            true,
+        )
+    }
+}
+
+impl fmt::Debug for SBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "SBox {}x{}", self.input_bits, self.output_bits)?;
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for PBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        grid(f, &self.permutation, self.len().clamp(1, 8), false)
+    }
+}
+
+impl fmt::Debug for PBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PBox {}", self.len())?;
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{heys_p_box, heys_s_box};
+
+    #[test]
+    fn test_display() {
+        let s_box = heys_s_box();
+        assert_eq!(
+            s_box.to_string(),
+            "  | 0 1 2 3\n\
+             --+--------\n\
+             0 | e 4 d 1\n\
+             4 | 2 f b 8\n\
+             8 | 3 a 6 c\n\
+             c | 5 9 0 7\n"
+        );
+        assert!(format!("{:?}", s_box).starts_with("SBox 4x4\n  | 0 1 2 3\n"));
+
+        let p_box = heys_p_box();
+        assert_eq!(
+            p_box.to_string(),
+            "  |  0  1  2  3  4  5  6  7\n\
+             --+------------------------\n\
+             0 |  1  5  9 13  2  6 10 14\n\
+             8 |  3  7 11 15  4  8 12 16\n"
+        );
+    }
+}
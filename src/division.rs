@@ -0,0 +1,116 @@
+use crate::{ConstantTimeSBox, SBox, Spn};
+
+fn reduce(mut vectors: Vec<u32>) -> Vec<u32> {
+    vectors.sort_unstable_by_key(|&k| (k.count_ones(), k));
+    vectors.dedup();
+
+    let mut result: Vec<u32> = Vec::new();
+    for k in vectors {
+        if !result.iter().any(|&m| m & !k == 0) {
+            result.push(k);
+        }
+    }
+
+    result
+}
+
+impl SBox {
+    pub fn division_trails(&self) -> Vec<Vec<u32>> {
+        let monomials: Vec<Vec<u32>> = (0..1u32 << self.output_bits)
+            .map(|v| {
+                let product: Vec<u32> = self.table.iter().map(|&y| (y & v == v) as u32).collect();
+                ConstantTimeSBox::moebius(&product)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|&(_, coefficient)| coefficient != 0)
+                    .map(|(w, _)| w as u32)
+                    .collect()
+            })
+            .collect();
+
+        (0..1u32 << self.input_bits)
+            .map(|u| {
+                let valid = (0..1u32 << self.output_bits)
+                    .filter(|&v| monomials[v as usize].iter().any(|&w| w & u == u))
+                    .collect();
+                reduce(valid)
+            })
+            .collect()
+    }
+}
+
+impl Spn {
+    pub fn balanced_bits(&self, active: u32, rounds: usize) -> Result<u32, &'static str> {
+        self.check_word_size()?;
+
+        let trails = self.s_box().division_trails();
+
+        let mut vectors = vec![active];
+        for _ in 0..rounds {
+            let mut next = Vec::new();
+            for &k in &vectors {
+                let mut outputs = vec![0];
+                for j in 0..self.s_box_count() {
+                    let trails = &trails[self.chunk(k, j) as usize];
+                    outputs = outputs
+                        .iter()
+                        .flat_map(|&y| trails.iter().map(move |&v| self.with_chunk(y, j, v)))
+                        .collect();
+                }
+                next.extend(outputs.into_iter().map(|y| self.p_box().encrypt_value(y)));
+            }
+            vectors = reduce(next);
+        }
+
+        Ok((0..self.block_size())
+            .map(|i| 1u32 << i)
+            .filter(|&bit| !vectors.iter().any(|&k| k & !bit == 0))
+            .fold(0, |acc, bit| acc | bit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+    use crate::{bits2num, num2bits, PBox};
+
+    #[test]
+    fn test_division_trails() {
+        let s_box = SBox::new(vec![vec![0, 3], vec![1, 2]]).unwrap();
+        let trails = s_box.division_trails();
+        assert_eq!(trails[0], vec![0]);
+        assert_eq!(trails[0b11], vec![0b11]);
+    }
+
+    #[test]
+    fn test_balanced_bits() {
+        let s_box = heys_s_box();
+        let p_box = PBox::new(vec![1, 5, 2, 6, 3, 7, 4, 8]).unwrap();
+        let round_keys = [0x3a, 0x94, 0xd6, 0x3f]
+            .map(|key| num2bits(key, 8))
+            .to_vec();
+        let spn = Spn::new(s_box, p_box, round_keys).unwrap();
+
+        assert_eq!(spn.balanced_bits(0xf0, 1), Ok(0xff));
+        for (active, rounds) in [(0xf0, 2), (0xfe, 2), (0xfe, 3)] {
+            let balanced = spn.balanced_bits(active, rounds).unwrap();
+            for constant in [0x00, 0x5a] {
+                let mut sum = 0;
+                for x in (0..1u32 << 8).filter(|&x| x & !active == 0) {
+                    let mut state = num2bits(x ^ (constant & !active), 8);
+                    for round in 0..rounds {
+                        state = spn.encrypt_round(&state, round);
+                    }
+                    sum ^= bits2num(&state);
+                }
+                assert_eq!(sum & balanced, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_present_is_rejected() {
+        assert!(Spn::present80(0).unwrap().balanced_bits(1, 1).is_err());
+    }
+}
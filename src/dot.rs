@@ -0,0 +1,89 @@
+use crate::{PBox, Spn};
+
+impl PBox {
+    pub fn to_dot(&self) -> String {
+        let n = self.permutation.len();
+
+        let mut result =
+            String::from("digraph pbox {\n    rankdir=TB;\n    node [shape=circle];\n");
+        for side in ["in", "out"] {
+            result.push_str("    { rank=same;");
+            for i in 0..n {
+                result.push_str(&format!(" {}{} [label=\"{}\"];", side, i, i + 1));
+            }
+            result.push_str(" }\n");
+        }
+        for (i, &num) in self.permutation.iter().enumerate() {
+            result.push_str(&format!("    in{} -> out{};\n", i, num - 1));
+        }
+        result.push_str("}\n");
+
+        result
+    }
+}
+
+impl Spn {
+    pub fn to_dot(&self) -> String {
+        let n = self.block_size();
+        let s_box_size = self.s_box().input_bits();
+
+        let mut result = String::from("digraph spn {\n    rankdir=TB;\n    node [shape=point];\n");
+        for round in 0..self.rounds() {
+            for s_box in 0..n / s_box_size {
+                result.push_str(&format!(
+                    "    subgraph cluster_r{}_s{} {{ label=\"S\";",
+                    round, s_box
+                ));
+                for i in s_box * s_box_size..(s_box + 1) * s_box_size {
+                    result.push_str(&format!(" r{}_{};", round, i));
+                }
+                result.push_str(" }\n");
+            }
+        }
+        result.push_str("    { rank=same;");
+        for i in 0..n {
+            result.push_str(&format!(" r{}_{};", self.rounds(), i));
+        }
+        result.push_str(" }\n");
+
+        for round in 0..self.rounds() {
+            for (i, &num) in self.p_box().permutation.iter().enumerate() {
+                result.push_str(&format!(
+                    "    r{}_{} -> r{}_{};\n",
+                    round,
+                    i,
+                    round + 1,
+                    num - 1
+                ));
+            }
+        }
+        result.push_str("}\n");
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{num2bits, SBox};
+
+    #[test]
+    fn test_dot() {
+        let p_box = PBox::new(vec![2, 1]).unwrap();
+        assert_eq!(
+            p_box.to_dot(),
+            "digraph pbox {\n    rankdir=TB;\n    node [shape=circle];\n    \
+             { rank=same; in0 [label=\"1\"]; in1 [label=\"2\"]; }\n    \
+             { rank=same; out0 [label=\"1\"]; out1 [label=\"2\"]; }\n    \
+             in0 -> out1;\n    in1 -> out0;\n}\n"
+        );
+
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let p_box = PBox::new(vec![1, 3, 2, 4]).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 4); 3]).unwrap();
+        let dot = spn.to_dot();
+        assert!(dot.contains("    subgraph cluster_r1_s1 { label=\"S\"; r1_2; r1_3; }\n"));
+        assert!(dot.contains("    r1_1 -> r2_2;\n"));
+    }
+}
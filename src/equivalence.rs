@@ -0,0 +1,205 @@
+use crate::{PBox, SBox};
+
+pub struct PermutationEquivalence {
+    pub input_permutation: PBox,
+    pub input_constant: u32,
+    pub output_permutation: PBox,
+    pub output_constant: u32,
+}
+
+impl PermutationEquivalence {
+    pub fn apply(&self, s_box: &SBox) -> SBox {
+        let table = (0..1u32 << s_box.input_bits())
+            .map(|x| {
+                let y = s_box.lookup(self.input_permutation.encrypt_value(x) ^ self.input_constant);
+                self.output_permutation.encrypt_value(y) ^ self.output_constant
+            })
+            .collect();
+
+        SBox::new(vec![table]).unwrap()
+    }
+}
+
+fn permutations(n: usize) -> Vec<Vec<u32>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for permutation in permutations(n - 1) {
+        for position in 0..n {
+            let mut permutation = permutation.clone();
+            permutation.insert(position, n as u32);
+            result.push(permutation);
+        }
+    }
+
+    result
+}
+
+fn columns(values: &[u32], bits: usize) -> Vec<Vec<bool>> {
+    (0..bits)
+        .map(|i| {
+            values
+                .iter()
+                .map(|&value| (value >> (bits - 1 - i)) & 1 == 1)
+                .collect()
+        })
+        .collect()
+}
+
+impl SBox {
+    pub fn permutation_equivalence(&self, other: &SBox) -> Option<PermutationEquivalence> {
+        if self.input_bits() != other.input_bits() || self.output_bits() != other.output_bits() {
+            return None;
+        }
+
+        let (n, m) = (self.input_bits(), self.output_bits());
+        let target: Vec<u32> = other.table.iter().map(|&y| y ^ other.table[0]).collect();
+        let target_columns = columns(&target, m);
+
+        for permutation in permutations(n) {
+            let input_permutation = PBox::new(permutation).ok()?;
+            for input_constant in 0..1u32 << n {
+                let values: Vec<u32> = (0..1u32 << n)
+                    .map(|x| self.lookup(input_permutation.encrypt_value(x) ^ input_constant))
+                    .collect();
+                let differences: Vec<u32> = values.iter().map(|&y| y ^ values[0]).collect();
+                if differences
+                    .iter()
+                    .zip(&target)
+                    .any(|(a, b)| a.count_ones() != b.count_ones())
+                {
+                    continue;
+                }
+
+                let mut used = vec![false; m];
+                let mut output = Vec::with_capacity(m);
+                for column in columns(&differences, m) {
+                    match (0..m).find(|&j| !used[j] && target_columns[j] == column) {
+                        Some(j) => {
+                            used[j] = true;
+                            output.push(j as u32 + 1);
+                        }
+                        None => break,
+                    }
+                }
+                if output.len() != m {
+                    continue;
+                }
+
+                let output_permutation = PBox::new(output).ok()?;
+                let output_constant = other.table[0] ^ output_permutation.encrypt_value(values[0]);
+                return Some(PermutationEquivalence {
+                    input_permutation,
+                    input_constant,
+                    output_permutation,
+                    output_constant,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub fn is_permutation_equivalent(&self, other: &SBox) -> bool {
+        self.permutation_equivalence(other).is_some()
+    }
+
+    pub fn fixed_points(&self) -> Vec<u32> {
+        (0..self.table.len() as u32)
+            .filter(|&x| self.table[x as usize] == x)
+            .collect()
+    }
+
+    pub fn opposite_fixed_points(&self) -> Vec<u32> {
+        let mask = (self.table.len() - 1) as u32;
+        (0..self.table.len() as u32)
+            .filter(|&x| self.table[x as usize] == !x & mask)
+            .collect()
+    }
+
+    pub fn fixed_point_free_variant(&self) -> Option<PermutationEquivalence> {
+        if self.input_bits != self.output_bits {
+            return None;
+        }
+
+        let n = self.input_bits;
+        let mask = (self.table.len() - 1) as u32;
+        let mut candidates = permutations(n);
+        candidates.sort();
+        for permutation in candidates {
+            let output_permutation = PBox::new(permutation).ok()?;
+            let mut used = vec![false; self.table.len()];
+            for (x, &y) in self.table.iter().enumerate() {
+                used[(output_permutation.encrypt_value(y) ^ x as u32) as usize] = true;
+            }
+
+            if let Some(constant) =
+                (0..=mask).find(|&c| !used[c as usize] && !used[(c ^ mask) as usize])
+            {
+                return Some(PermutationEquivalence {
+                    input_permutation: PBox::new((1..=n as u32).collect()).ok()?,
+                    input_constant: 0,
+                    output_permutation,
+                    output_constant: constant,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+
+    #[test]
+    fn test_permutation_equivalence() {
+        let witness = PermutationEquivalence {
+            input_permutation: PBox::new(vec![2, 4, 1, 3]).unwrap(),
+            input_constant: 0x5,
+            output_permutation: PBox::new(vec![3, 1, 4, 2]).unwrap(),
+            output_constant: 0x9,
+        };
+        let other = witness.apply(&heys_s_box());
+
+        let found = heys_s_box().permutation_equivalence(&other).unwrap();
+        assert!(found.apply(&heys_s_box()) == other);
+        assert!(other.is_permutation_equivalent(&heys_s_box()));
+    }
+
+    #[test]
+    fn test_fixed_point_free_variant() {
+        let identity = SBox::from_flat((0..16).collect(), 4, 4).unwrap();
+        assert_eq!(identity.fixed_points().len(), 16);
+        let witness = identity.fixed_point_free_variant().unwrap();
+        assert_eq!(witness.output_permutation.encrypt_value(0b0001), 0b0001);
+        assert_eq!(witness.output_constant, 1);
+
+        let witness = heys_s_box().fixed_point_free_variant().unwrap();
+        let variant = witness.apply(&heys_s_box());
+        assert!(variant.fixed_points().is_empty());
+        assert!(variant.opposite_fixed_points().is_empty());
+        assert!(variant.is_permutation_equivalent(&heys_s_box()));
+        assert_eq!(
+            variant.differential_uniformity(),
+            heys_s_box().differential_uniformity()
+        );
+        assert_eq!(
+            heys_s_box().fixed_points().len() + heys_s_box().opposite_fixed_points().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_not_equivalent() {
+        let identity = SBox::from_flat((0..16).collect(), 4, 4).unwrap();
+        assert!(!heys_s_box().is_permutation_equivalent(&identity));
+
+        let smaller = SBox::from_flat(vec![2, 0, 3, 1], 2, 2).unwrap();
+        assert!(heys_s_box().permutation_equivalence(&smaller).is_none());
+    }
+}
@@ -0,0 +1,295 @@
+use std::ptr;
+use std::slice;
+
+use crate::{bits2num, num2bits, PBox, SBox, Spn};
+
+fn into_raw<T, E>(result: Result<T, E>) -> *mut T {
+    result.map_or(ptr::null_mut(), |value| Box::into_raw(Box::new(value)))
+}
+
+unsafe fn copy_out<T: Copy>(values: &[T], out: *mut T, out_len: usize) -> usize {
+    if !out.is_null() {
+        let count = values.len().min(out_len);
+        ptr::copy_nonoverlapping(values.as_ptr(), out, count);
+    }
+
+    values.len()
+}
+
+unsafe fn transform_buffer(
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+    transform: impl Fn(u32) -> u32,
+) {
+    let input = slice::from_raw_parts(input, len);
+    let output = slice::from_raw_parts_mut(output, len);
+    for (y, &x) in output.iter_mut().zip(input) {
+        *y = transform(x);
+    }
+}
+
+/// # Safety
+/// `table` must point to `len` readable values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_new(table: *const u32, len: usize) -> *mut SBox {
+    into_raw(SBox::new(vec![slice::from_raw_parts(table, len).to_vec()]))
+}
+
+/// # Safety
+/// `s_box` must come from `psboxs_sbox_new` and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_free(s_box: *mut SBox) {
+    if !s_box.is_null() {
+        drop(Box::from_raw(s_box));
+    }
+}
+
+/// # Safety
+/// `s_box` must be a live pointer from `psboxs_sbox_new`.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_input_bits(s_box: *const SBox) -> usize {
+    (*s_box).input_bits
+}
+
+/// # Safety
+/// `s_box` must be a live pointer from `psboxs_sbox_new`.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_output_bits(s_box: *const SBox) -> usize {
+    (*s_box).output_bits
+}
+
+/// # Safety
+/// `s_box` must be live; `input` and `output` must each hold `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_encrypt(
+    s_box: *const SBox,
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+) {
+    let s_box = &*s_box;
+    transform_buffer(input, output, len, |x| {
+        bits2num(&s_box.encrypt(&num2bits(x, s_box.input_bits)))
+    });
+}
+
+/// # Safety
+/// `s_box` must be live; `input` and `output` must each hold `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_decrypt(
+    s_box: *const SBox,
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+) {
+    let s_box = &*s_box;
+    transform_buffer(input, output, len, |y| {
+        bits2num(&s_box.decrypt(&num2bits(y, s_box.output_bits)))
+    });
+}
+
+/// Copies up to `out_len` table entries into `out` and returns the table size.
+///
+/// # Safety
+/// `s_box` must be live; `out` must be null or hold `out_len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_table(
+    s_box: *const SBox,
+    out: *mut u32,
+    out_len: usize,
+) -> usize {
+    copy_out(&(*s_box).table, out, out_len)
+}
+
+/// Copies up to `out_len` entries of the row-major DDT into `out` and returns its size.
+///
+/// # Safety
+/// `s_box` must be live; `out` must be null or hold `out_len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_ddt(
+    s_box: *const SBox,
+    out: *mut u32,
+    out_len: usize,
+) -> usize {
+    copy_out(&(*s_box).ddt().concat(), out, out_len)
+}
+
+/// Copies up to `out_len` entries of the row-major LAT into `out` and returns its size.
+///
+/// # Safety
+/// `s_box` must be live; `out` must be null or hold `out_len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_sbox_lat(
+    s_box: *const SBox,
+    out: *mut i32,
+    out_len: usize,
+) -> usize {
+    copy_out(&(*s_box).lat().concat(), out, out_len)
+}
+
+/// Returns null for an invalid permutation or one wider than 32 bits.
+///
+/// # Safety
+/// `permutation` must point to `len` readable values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_pbox_new(permutation: *const u32, len: usize) -> *mut PBox {
+    into_raw(
+        PBox::new(slice::from_raw_parts(permutation, len).to_vec())
+            .and_then(|p_box| p_box.check_word_size().map(|_| p_box)),
+    )
+}
+
+/// # Safety
+/// `p_box` must come from `psboxs_pbox_new` and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_pbox_free(p_box: *mut PBox) {
+    if !p_box.is_null() {
+        drop(Box::from_raw(p_box));
+    }
+}
+
+/// # Safety
+/// `p_box` must be live; `input` and `output` must each hold `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_pbox_encrypt(
+    p_box: *const PBox,
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+) {
+    let p_box = &*p_box;
+    transform_buffer(input, output, len, |x| {
+        bits2num(&p_box.encrypt(&num2bits(x, p_box.len())))
+    });
+}
+
+/// # Safety
+/// `p_box` must be live; `input` and `output` must each hold `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_pbox_decrypt(
+    p_box: *const PBox,
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+) {
+    let p_box = &*p_box;
+    transform_buffer(input, output, len, |y| {
+        bits2num(&p_box.decrypt(&num2bits(y, p_box.len())))
+    });
+}
+
+/// Builds an SPN from copies of the given boxes; returns null on invalid input.
+///
+/// # Safety
+/// `s_box` and `p_box` must be live; `round_keys` must hold `round_key_count` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_spn_new(
+    s_box: *const SBox,
+    p_box: *const PBox,
+    round_keys: *const u32,
+    round_key_count: usize,
+) -> *mut Spn {
+    let (s_box, p_box) = (&*s_box, &*p_box);
+    let round_keys = slice::from_raw_parts(round_keys, round_key_count)
+        .iter()
+        .map(|&key| num2bits(key, p_box.len()))
+        .collect();
+
+    into_raw(Spn::new(s_box.clone(), p_box.clone(), round_keys))
+}
+
+/// # Safety
+/// `spn` must come from `psboxs_spn_new` and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_spn_free(spn: *mut Spn) {
+    if !spn.is_null() {
+        drop(Box::from_raw(spn));
+    }
+}
+
+/// # Safety
+/// `spn` must be live; `input` and `output` must each hold `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_spn_encrypt(
+    spn: *const Spn,
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+) {
+    let spn = &*spn;
+    transform_buffer(input, output, len, |x| {
+        bits2num(&spn.encrypt(&num2bits(x, spn.block_size())))
+    });
+}
+
+/// # Safety
+/// `spn` must be live; `input` and `output` must each hold `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn psboxs_spn_decrypt(
+    spn: *const Spn,
+    input: *const u32,
+    output: *mut u32,
+    len: usize,
+) {
+    let spn = &*spn;
+    transform_buffer(input, output, len, |y| {
+        bits2num(&spn.decrypt(&num2bits(y, spn.block_size())))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_permutation, heys_table, HEYS_ROUND_KEYS};
+
+    #[test]
+    fn test_ffi() {
+        let table = heys_table();
+        let permutation = heys_permutation();
+        let round_keys = HEYS_ROUND_KEYS;
+
+        unsafe {
+            let s_box = psboxs_sbox_new(table.as_ptr(), table.len());
+            assert!(!s_box.is_null());
+            assert_eq!(psboxs_sbox_input_bits(s_box), 4);
+
+            let mut output = [0; 2];
+            psboxs_sbox_encrypt(s_box, [0x6, 0xf].as_ptr(), output.as_mut_ptr(), 2);
+            assert_eq!(output, [0xb, 0x7]);
+
+            assert_eq!(psboxs_sbox_ddt(s_box, ptr::null_mut(), 0), 256);
+            let mut ddt = [0; 256];
+            psboxs_sbox_ddt(s_box, ddt.as_mut_ptr(), ddt.len());
+            assert_eq!(ddt[0xb * 16 + 0x2], 8);
+
+            let p_box = psboxs_pbox_new(permutation.as_ptr(), permutation.len());
+            let spn = psboxs_spn_new(s_box, p_box, round_keys.as_ptr(), round_keys.len());
+            assert!(!spn.is_null());
+            assert!((*spn).p_box() == &*p_box);
+            assert_eq!((*spn).s_box().table, (*s_box).table);
+
+            let (mut encrypted, mut decrypted) = ([0; 1], [0; 1]);
+            psboxs_spn_encrypt(spn, [0x26b7].as_ptr(), encrypted.as_mut_ptr(), 1);
+            psboxs_spn_decrypt(spn, encrypted.as_ptr(), decrypted.as_mut_ptr(), 1);
+            assert_eq!(decrypted, [0x26b7]);
+
+            psboxs_spn_free(spn);
+            psboxs_pbox_free(p_box);
+            psboxs_sbox_free(s_box);
+
+            assert!(psboxs_pbox_new([1, 1].as_ptr(), 2).is_null());
+            let wide: Vec<u32> = (1..=64).collect();
+            assert!(psboxs_pbox_new(wide.as_ptr(), wide.len()).is_null());
+        }
+    }
+
+    #[test]
+    fn test_header_is_current() {
+        assert_eq!(
+            include_str!("../include/psboxs.h"),
+            include_str!(concat!(env!("OUT_DIR"), "/psboxs.h")),
+            "include/psboxs.h is stale, regenerate it with cbindgen"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{bits2num, num2bits, SBox};
+
+pub struct SBoxN<const IN: usize, const OUT: usize> {
+    s_box: SBox,
+}
+
+impl<const IN: usize, const OUT: usize> SBoxN<IN, OUT> {
+    pub fn new(table: Vec<Vec<u32>>) -> Result<Self, &'static str> {
+        Self::from_s_box(SBox::new(table)?)
+    }
+
+    pub fn from_s_box(s_box: SBox) -> Result<Self, &'static str> {
+        if s_box.input_bits() != IN || s_box.output_bits() != OUT {
+            return Err("table size does not match s-box width");
+        }
+
+        Ok(SBoxN { s_box })
+    }
+
+    pub fn as_s_box(&self) -> &SBox {
+        &self.s_box
+    }
+
+    pub fn into_inner(self) -> SBox {
+        self.s_box
+    }
+
+    fn transform<const FROM: usize, const TO: usize>(
+        bits: &[bool; FROM],
+        table: &[u32],
+    ) -> [bool; TO] {
+        let mut index = 0;
+        for &bit in bits {
+            index = (index << 1) | (bit as usize);
+        }
+
+        let value = table[index];
+        let mut result = [false; TO];
+        for (i, bit) in result.iter_mut().enumerate() {
+            *bit = (value >> (TO - 1 - i)) & 1 == 1;
+        }
+
+        result
+    }
+
+    pub fn encrypt(&self, bits: &[bool; IN]) -> [bool; OUT] {
+        Self::transform(bits, &self.s_box.table)
+    }
+
+    pub fn decrypt(&self, bits: &[bool; OUT]) -> [bool; IN] {
+        Self::transform(bits, self.s_box.inverse_table())
+    }
+}
+
+pub struct ConstSBox<const N: usize> {
+    table: [u32; N],
+    inverse_table: [u32; N],
+    bits: usize,
+}
+
+impl<const N: usize> ConstSBox<N> {
+    pub const fn new(table: [u32; N]) -> Result<Self, &'static str> {
+        if N < 2 || !N.is_power_of_two() {
+            return Err("invalid table");
+        }
+
+        let mut inverse_table = [0; N];
+        let mut used = [false; N];
+        let mut x = 0;
+        while x < N {
+            let y = table[x] as usize;
+            if y >= N || used[y] {
+                return Err("invalid table");
+            }
+
+            used[y] = true;
+            inverse_table[y] = x as u32;
+            x += 1;
+        }
+
+        Ok(ConstSBox {
+            table,
+            inverse_table,
+            bits: N.trailing_zeros() as usize,
+        })
+    }
+
+    pub fn table(&self) -> &[u32; N] {
+        &self.table
+    }
+
+    pub fn input_bits(&self) -> usize {
+        self.bits
+    }
+
+    pub fn output_bits(&self) -> usize {
+        self.bits
+    }
+
+    pub const fn lookup(&self, x: u32) -> u32 {
+        self.table[x as usize]
+    }
+
+    pub const fn lookup_inv(&self, y: u32) -> u32 {
+        self.inverse_table[y as usize]
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(self.lookup(bits2num(bits)), self.bits)
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(self.lookup_inv(bits2num(bits)), self.bits)
+    }
+}
+
+pub struct PBoxN<const N: usize> {
+    permutation: [u32; N],
+    inverse_permutation: [u32; N],
+}
+
+impl<const N: usize> PBoxN<N> {
+    pub const fn new(permutation: [u32; N]) -> Result<Self, &'static str> {
+        let mut inverse_permutation = [0; N];
+        let mut i = 0;
+        while i < N {
+            let num = permutation[i] as usize;
+            if num == 0 || num > N || inverse_permutation[num - 1] != 0 {
+                return Err("invalid permutation");
+            }
+
+            inverse_permutation[num - 1] = i as u32 + 1;
+            i += 1;
+        }
+
+        Ok(PBoxN {
+            permutation,
+            inverse_permutation,
+        })
+    }
+
+    pub fn permutation(&self) -> &[u32; N] {
+        &self.permutation
+    }
+
+    fn transform(bits: &[bool; N], permutation: &[u32; N]) -> [bool; N] {
+        let mut result = [false; N];
+        for (&bit, &num) in bits.iter().zip(permutation) {
+            result[(num - 1) as usize] = bit;
+        }
+
+        result
+    }
+
+    pub fn encrypt(&self, bits: &[bool; N]) -> [bool; N] {
+        Self::transform(bits, &self.permutation)
+    }
+
+    pub fn decrypt(&self, bits: &[bool; N]) -> [bool; N] {
+        Self::transform(bits, &self.inverse_permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_table;
+
+    #[test]
+    fn test_fixed_width() {
+        let s_box =
+            SBoxN::<4, 4>::new(heys_table().chunks(4).map(<[u32]>::to_vec).collect()).unwrap();
+        let p_box = PBoxN::new([2, 4, 1, 3]).unwrap();
+
+        let a = [false, true, true, false];
+        let b = p_box.encrypt(&s_box.encrypt(&a));
+        assert_eq!(b, [true, true, true, false]);
+        assert_eq!(s_box.decrypt(&p_box.decrypt(&b)), a);
+
+        assert!(SBoxN::<8, 8>::new(vec![vec![0, 1], vec![2, 3]]).is_err());
+    }
+
+    static HEYS_S_BOX: ConstSBox<16> = crate::sbox![
+        0xe, 0x4, 0xd, 0x1, 0x2, 0xf, 0xb, 0x8, 0x3, 0xa, 0x6, 0xc, 0x5, 0x9, 0x0, 0x7
+    ];
+    static ROTATION: PBoxN<4> = crate::pbox![2, 3, 4, 1];
+
+    #[test]
+    fn test_macros() {
+        assert_eq!(HEYS_S_BOX.lookup(0x6), 0xb);
+        assert_eq!(HEYS_S_BOX.lookup_inv(0xb), 0x6);
+        for x in 0..16 {
+            assert_eq!(HEYS_S_BOX.lookup_inv(HEYS_S_BOX.lookup(x)), x);
+        }
+
+        let a = num2bits(0b0110, 4);
+        let b = HEYS_S_BOX.encrypt(&a);
+        assert_eq!(bits2num(&b), 0xb);
+        assert_eq!(HEYS_S_BOX.decrypt(&b), a);
+        assert_eq!(HEYS_S_BOX.table, heys_table());
+
+        assert_eq!(
+            ROTATION.encrypt(&[true, false, false, false]),
+            [false, true, false, false]
+        );
+        assert!(ConstSBox::new([0, 1, 1, 3]).is_err());
+        assert!(PBoxN::new([1, 1, 2]).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use crate::reference::{HEYS_S_BOX, PRESENT_S_BOX};
+use crate::{num2bits, PBox, SBox, Spn};
+
+pub const HEYS_ROUND_KEYS: [u32; 5] = [0x3a94, 0xa94d, 0x94d6, 0x4d63, 0xd63f];
+
+pub fn heys_table() -> [u32; 16] {
+    HEYS_S_BOX
+}
+
+pub fn heys_s_box() -> SBox {
+    SBox::from_flat(heys_table().to_vec(), 4, 4).unwrap()
+}
+
+pub fn present_s_box() -> SBox {
+    SBox::from_flat(PRESENT_S_BOX.to_vec(), 4, 4).unwrap()
+}
+
+pub fn heys_permutation() -> Vec<u32> {
+    vec![1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15, 4, 8, 12, 16]
+}
+
+pub fn heys_p_box() -> PBox {
+    PBox::new(heys_permutation()).unwrap()
+}
+
+pub fn heys_spn() -> Spn {
+    let round_keys = HEYS_ROUND_KEYS
+        .iter()
+        .map(|&key| num2bits(key, 16))
+        .collect();
+
+    Spn::new(heys_s_box(), heys_p_box(), round_keys).unwrap()
+}
@@ -0,0 +1,191 @@
+use crate::block_cipher::xor;
+use crate::{cbc_mac, BlockCipher};
+
+pub struct Ffx<C: BlockCipher> {
+    cipher: C,
+    radix: u32,
+    rounds: usize,
+}
+
+impl<C: BlockCipher> Ffx<C> {
+    pub fn new(cipher: C, radix: u32) -> Result<Ffx<C>, &'static str> {
+        if !(2..=36).contains(&radix) {
+            return Err("radix must be between 2 and 36");
+        }
+
+        Ok(Ffx {
+            cipher,
+            radix,
+            rounds: 10,
+        })
+    }
+
+    pub fn with_rounds(mut self, rounds: usize) -> Ffx<C> {
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    fn number(&self, digits: &[u32]) -> u64 {
+        digits
+            .iter()
+            .fold(0, |acc, &digit| acc * self.radix as u64 + digit as u64)
+    }
+
+    fn digits(&self, mut number: u64, length: usize) -> Vec<u32> {
+        let mut result = vec![0; length];
+        for digit in result.iter_mut().rev() {
+            *digit = (number % self.radix as u64) as u32;
+            number /= self.radix as u64;
+        }
+
+        result
+    }
+
+    fn round_value(&self, round: usize, length: usize, half: u64, modulus: u64) -> u64 {
+        let n = self.cipher.block_size();
+        let mut input: Vec<bool> = [round as u8, length as u8]
+            .iter()
+            .chain(&half.to_be_bytes())
+            .flat_map(|&byte| (0..8).map(move |i| (byte >> (7 - i)) & 1 == 1))
+            .collect();
+        input.resize(input.len().div_ceil(n) * n, false);
+
+        let tag = cbc_mac(&self.cipher, &input).unwrap();
+        let mut stream = tag.clone();
+        let mut counter = 1u64;
+        while stream.len() < 96 {
+            let block: Vec<bool> = (0..n)
+                .map(|i| n - 1 - i < 64 && (counter >> (n - 1 - i)) & 1 == 1)
+                .collect();
+            stream.extend(self.cipher.encrypt_block(&xor(&tag, &block)));
+            counter += 1;
+        }
+
+        let value = stream[..96]
+            .iter()
+            .fold(0u128, |acc, &bit| (acc << 1) | bit as u128);
+        (value % modulus as u128) as u64
+    }
+
+    fn check(&self, digits: &[u32]) -> Result<(u64, u64), &'static str> {
+        if digits.len() < 2 || digits.len() > 255 {
+            return Err("invalid length");
+        }
+        if digits.iter().any(|&digit| digit >= self.radix) {
+            return Err("digit out of range");
+        }
+
+        let (u, v) = (digits.len() / 2, digits.len() - digits.len() / 2);
+        let modulus = |m: usize| (self.radix as u64).checked_pow(m as u32);
+        match (modulus(u), modulus(v)) {
+            (Some(a), Some(b)) => Ok((a, b)),
+            _ => Err("domain does not fit in 64 bits"),
+        }
+    }
+
+    pub fn encrypt(&self, digits: &[u32]) -> Result<Vec<u32>, &'static str> {
+        let moduli = self.check(digits)?;
+        let length = digits.len();
+        let u = length / 2;
+
+        let (mut a, mut b) = (digits[..u].to_vec(), digits[u..].to_vec());
+        for round in 0..self.rounds {
+            let (m, modulus) = match round % 2 {
+                0 => (u, moduli.0),
+                _ => (length - u, moduli.1),
+            };
+            let value = self.round_value(round, length, self.number(&b), modulus);
+            let c = (self.number(&a) % modulus + value) % modulus;
+            (a, b) = (b, self.digits(c, m));
+        }
+
+        Ok(a.into_iter().chain(b).collect())
+    }
+
+    pub fn decrypt(&self, digits: &[u32]) -> Result<Vec<u32>, &'static str> {
+        let moduli = self.check(digits)?;
+        let length = digits.len();
+        let split = match self.rounds % 2 {
+            0 => length / 2,
+            _ => length - length / 2,
+        };
+
+        let (mut a, mut b) = (digits[..split].to_vec(), digits[split..].to_vec());
+        for round in (0..self.rounds).rev() {
+            let (m, modulus) = match round % 2 {
+                0 => (length / 2, moduli.0),
+                _ => (length - length / 2, moduli.1),
+            };
+            let value = self.round_value(round, length, self.number(&a), modulus);
+            let c = (self.number(&b) + modulus - value) % modulus;
+            (a, b) = (self.digits(c, m), a);
+        }
+
+        Ok(a.into_iter().chain(b).collect())
+    }
+
+    fn transform_str(
+        &self,
+        text: &str,
+        f: impl Fn(&[u32]) -> Result<Vec<u32>, &'static str>,
+    ) -> Result<String, &'static str> {
+        let digits = text
+            .chars()
+            .map(|c| c.to_digit(self.radix).ok_or("digit out of range"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(f(&digits)?
+            .into_iter()
+            .map(|digit| char::from_digit(digit, self.radix).unwrap())
+            .collect())
+    }
+
+    pub fn encrypt_str(&self, text: &str) -> Result<String, &'static str> {
+        self.transform_str(text, |digits| self.encrypt(digits))
+    }
+
+    pub fn decrypt_str(&self, text: &str) -> Result<String, &'static str> {
+        self.transform_str(text, |digits| self.decrypt(digits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MiniAes;
+
+    #[test]
+    fn test_ffx_card_number() {
+        let ffx = Ffx::new(MiniAes::new(0xc3f0).unwrap(), 10).unwrap();
+        let card = "4111111111111111";
+        let encrypted = ffx.encrypt_str(card).unwrap();
+        assert_eq!(encrypted.len(), 16);
+        assert!(encrypted.chars().all(|c| c.is_ascii_digit()));
+        assert_ne!(encrypted, card);
+        assert_eq!(ffx.decrypt_str(&encrypted).unwrap(), card);
+
+        let odd = ffx.with_rounds(7);
+        let encrypted = odd.encrypt_str("12345").unwrap();
+        assert_eq!(odd.decrypt_str(&encrypted).unwrap(), "12345");
+
+        assert!(odd.encrypt_str("12a45").is_err());
+        assert!(odd.encrypt(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_ffx_permutation() {
+        let ffx = Ffx::new(MiniAes::new(0x1234).unwrap(), 10).unwrap();
+        let mut seen = vec![false; 1000];
+        for x in 0..1000u32 {
+            let digits = [x / 100, x / 10 % 10, x % 10];
+            let y = ffx.encrypt(&digits).unwrap();
+            seen[(y[0] * 100 + y[1] * 10 + y[2]) as usize] = true;
+            assert_eq!(ffx.decrypt(&y).unwrap(), digits);
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{bits2num, num2bits, parse_kat, parse_rsp, CsvOptions, PBox, SBox, Spn};
+
+fn argsort(bytes: &[u8]) -> Vec<u32> {
+    let mut order: Vec<u32> = (0..bytes.len() as u32).collect();
+    order.sort_by_key(|&i| bytes[i as usize]);
+    order
+}
+
+fn check_s_box(s_box: &SBox) {
+    let bytes = s_box.to_bytes();
+    assert!(SBox::from_bytes(&bytes).unwrap() == *s_box);
+
+    let size = 1u32 << s_box.input_bits();
+    let mut used = vec![false; size as usize];
+    for x in 0..size {
+        let y = s_box.lookup(x);
+        assert_eq!(
+            bits2num(&s_box.encrypt(&num2bits(x, s_box.input_bits()))),
+            y
+        );
+        used[y as usize] = true;
+    }
+    if used.iter().all(|&u| u) {
+        for x in 0..size {
+            assert_eq!(s_box.lookup_inv(s_box[x]), x);
+        }
+    }
+}
+
+fn check_p_box(p_box: &PBox) {
+    assert!(PBox::from_bytes(&p_box.to_bytes().unwrap()).unwrap() == *p_box);
+
+    let bits: Vec<bool> = (0..p_box.len()).map(|i| i % 3 == 0).collect();
+    assert_eq!(p_box.decrypt(&p_box.encrypt(&bits)), bits);
+}
+
+pub fn fuzz_parsers(data: &[u8]) {
+    let text = String::from_utf8_lossy(data);
+
+    let parsed = [
+        SBox::from_c_array(&text),
+        SBox::from_python_list(&text),
+        SBox::from_hex_dump(&text),
+        SBox::from_pla(&text),
+        SBox::from_sage(&text),
+        SBox::from_csv(&text, &CsvOptions::default()),
+        SBox::from_bytes(data),
+        text.parse(),
+    ];
+    for s_box in parsed.iter().flatten() {
+        check_s_box(s_box);
+    }
+
+    for p_box in [
+        PBox::from_csv(&text, &CsvOptions::default()),
+        PBox::from_bytes(data),
+        text.parse(),
+    ]
+    .iter()
+    .flatten()
+    {
+        check_p_box(p_box);
+    }
+
+    if let Ok(spn) = Spn::from_bytes(data) {
+        assert_eq!(
+            Spn::from_bytes(&spn.to_bytes().unwrap())
+                .unwrap()
+                .to_bytes(),
+            spn.to_bytes()
+        );
+    }
+    let _ = parse_kat(&text);
+    let _ = parse_rsp(&text);
+}
+
+pub fn fuzz_s_box(data: &[u8]) {
+    let Some((&first, rest)) = data.split_first() else {
+        return;
+    };
+
+    let n = (first % 7 + 2) as usize;
+    if rest.len() < 1 << n {
+        return;
+    }
+
+    let table: Vec<u32> = rest[..1 << n]
+        .iter()
+        .map(|&b| b as u32 & ((1 << n) - 1))
+        .collect();
+    if let Ok(s_box) = SBox::new(vec![table]) {
+        check_s_box(&s_box);
+    }
+}
+
+pub fn fuzz_p_box(data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+
+    let p_box = PBox::new(
+        argsort(&data[..data.len().min(32)])
+            .iter()
+            .map(|&i| i + 1)
+            .collect(),
+    )
+    .unwrap();
+    check_p_box(&p_box);
+    assert_eq!(
+        p_box.compose(&p_box.pow(-1)).unwrap().permutation,
+        (1..=p_box.len() as u32).collect::<Vec<u32>>()
+    );
+}
+
+pub fn fuzz_spn(data: &[u8]) {
+    if data.len() < 3 {
+        return;
+    }
+
+    let s_box_size = (data[0] % 3 + 2) as usize;
+    let n = s_box_size * (data[1] % 4 + 1) as usize;
+    let rounds = (data[2] % 4 + 1) as usize;
+    let rest = &data[3..];
+    if rest.len() < (1 << s_box_size) + n + 4 * (rounds + 1) {
+        return;
+    }
+
+    let s_box = SBox::new(vec![argsort(&rest[..1 << s_box_size])]).unwrap();
+    let rest = &rest[1 << s_box_size..];
+    let p_box = PBox::new(argsort(&rest[..n]).iter().map(|&i| i + 1).collect()).unwrap();
+    let round_keys = rest[n..]
+        .chunks_exact(4)
+        .take(rounds + 1)
+        .map(|key| {
+            num2bits(
+                u32::from_be_bytes(key.try_into().unwrap()) & ((1 << n) - 1),
+                n,
+            )
+        })
+        .collect();
+    let spn = Spn::new(s_box, p_box, round_keys).unwrap();
+
+    let bits = num2bits(bits2num(&spn.round_keys()[0]) ^ 0x5a5a, n);
+    let cipher = spn.encrypt(&bits);
+    assert_eq!(spn.decrypt(&cipher), bits);
+    assert_eq!(spn.with_t_tables().unwrap().encrypt(&bits), cipher);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzz_targets() {
+        let inputs: Vec<Vec<u8>> = vec![
+            Vec::new(),
+            b"[2, 0, 3, 1]".to_vec(),
+            b"0x2, 0x0, 0x3, 0x1".to_vec(),
+            SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap().to_bytes(),
+            (0..=255).collect(),
+            (0..=255).rev().collect(),
+        ];
+        for data in &inputs {
+            fuzz_parsers(data);
+            fuzz_s_box(data);
+            fuzz_p_box(data);
+            fuzz_spn(data);
+        }
+    }
+}
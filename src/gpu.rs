@@ -0,0 +1,385 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{bits2num, num2bits, SBox, Spn};
+
+const WORKGROUP_SIZE: u32 = 64;
+const MAX_WORKGROUPS: u32 = 65535;
+const DDT_ROWS_PER_DISPATCH: usize = 256;
+const MAX_CODEBOOK_BITS: usize = 24;
+
+const DDT_SHADER: &str = "
+@group(0) @binding(0) var<storage, read> table: array<u32>;
+@group(0) @binding(1) var<storage, read> differences: array<u32>;
+@group(0) @binding(2) var<storage, read_write> counts: array<atomic<u32>>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let index = id.x + id.y * groups.x * 64u;
+    let size = arrayLength(&table);
+    if (index >= size * arrayLength(&differences)) {
+        return;
+    }
+
+    let row = index / size;
+    let x = index % size;
+    let y = table[x] ^ table[x ^ differences[row]];
+    atomicAdd(&counts[row * size + y], 1u);
+}
+";
+
+const SPN_SHADER: &str = "
+@group(0) @binding(0) var<storage, read> table: array<u32>;
+@group(0) @binding(1) var<storage, read> permutation: array<u32>;
+@group(0) @binding(2) var<storage, read> keys: array<u32>;
+@group(0) @binding(3) var<storage, read> blocks: array<u32>;
+@group(0) @binding(4) var<storage, read_write> result: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let index = id.x + id.y * groups.x * 64u;
+    if (index >= arrayLength(&blocks)) {
+        return;
+    }
+
+    let n = arrayLength(&permutation);
+    let s = firstTrailingBit(arrayLength(&table));
+    let rounds = arrayLength(&keys) - 1u;
+
+    var state = blocks[index];
+    for (var round = 0u; round < rounds; round++) {
+        state ^= keys[round];
+
+        var substituted = 0u;
+        for (var j = 0u; j < n / s; j++) {
+            let shift = n - (j + 1u) * s;
+            substituted |= table[(state >> shift) & ((1u << s) - 1u)] << shift;
+        }
+
+        var permuted = 0u;
+        for (var i = 0u; i < n; i++) {
+            permuted |= ((substituted >> (n - 1u - i)) & 1u) << (n - permutation[i]);
+        }
+        state = permuted;
+    }
+
+    result[index] = state ^ keys[rounds];
+}
+";
+
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+fn to_bytes(values: &[u32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+impl Gpu {
+    async fn request() -> Option<Gpu> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions::default())
+            .await
+            .ok()?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                required_limits: adapter.limits(),
+                ..Default::default()
+            })
+            .await
+            .ok()?;
+
+        Some(Gpu { device, queue })
+    }
+
+    fn run(
+        &self,
+        shader: &str,
+        inputs: &[&[u32]],
+        output_len: usize,
+        invocations: usize,
+    ) -> Option<Vec<u32>> {
+        let groups = (invocations as u32).div_ceil(WORKGROUP_SIZE);
+        let (x, y) = (groups.min(MAX_WORKGROUPS), groups.div_ceil(MAX_WORKGROUPS));
+
+        let module = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(shader.into()),
+            });
+        let pipeline = self
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+
+        let mut buffers: Vec<wgpu::Buffer> = inputs
+            .iter()
+            .map(|values| {
+                self.device.create_buffer_init(&BufferInitDescriptor {
+                    label: None,
+                    contents: &to_bytes(values),
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+            })
+            .collect();
+        let size = 4 * output_len as u64;
+        buffers.push(self.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: &vec![0; size as usize],
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        }));
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(x, y, 1);
+        }
+        encoder.copy_buffer_to_buffer(buffers.last()?, 0, &staging, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        staging.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+        receiver.recv().ok()?.ok()?;
+
+        let result = staging
+            .get_mapped_range(..)
+            .ok()?
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+
+        Some(result)
+    }
+
+    fn ddt_rows(&self, s_box: &SBox, differences: &[u32]) -> Option<Vec<Vec<u32>>> {
+        let size = s_box.table.len();
+        let mut rows = Vec::with_capacity(differences.len());
+        for chunk in differences.chunks(DDT_ROWS_PER_DISPATCH) {
+            let len = chunk.len() * size;
+            let counts = self.run(DDT_SHADER, &[&s_box.table, chunk], len, len)?;
+            rows.extend(counts.chunks(size).map(|row| row.to_vec()));
+        }
+
+        Some(rows)
+    }
+
+    fn encrypt_many(&self, spn: &Spn, blocks: &[u32]) -> Option<Vec<u32>> {
+        let keys: Vec<u32> = spn.round_keys().iter().map(|key| bits2num(key)).collect();
+
+        self.run(
+            SPN_SHADER,
+            &[&spn.s_box().table, &spn.p_box().permutation, &keys, blocks],
+            blocks.len(),
+            blocks.len(),
+        )
+    }
+}
+
+pub struct Accelerator {
+    gpu: Option<Gpu>,
+}
+
+impl Accelerator {
+    pub fn new() -> Accelerator {
+        Accelerator {
+            gpu: pollster::block_on(Gpu::request()),
+        }
+    }
+
+    pub fn cpu() -> Accelerator {
+        Accelerator { gpu: None }
+    }
+
+    pub fn has_gpu(&self) -> bool {
+        self.gpu.is_some()
+    }
+
+    fn spn_on_gpu(&self, spn: &Spn) -> Option<&Gpu> {
+        let fits = spn.block_size() <= 32 && spn.s_box().input_bits() < 32;
+        self.gpu.as_ref().filter(|_| fits)
+    }
+
+    pub fn ddt_rows(&self, s_box: &SBox, differences: &[u32]) -> Vec<Vec<u32>> {
+        self.gpu
+            .as_ref()
+            .and_then(|gpu| gpu.ddt_rows(s_box, differences))
+            .unwrap_or_else(|| differences.iter().map(|&a| s_box.ddt_row(a)).collect())
+    }
+
+    pub fn ddt(&self, s_box: &SBox) -> Vec<Vec<u32>> {
+        let differences: Vec<u32> = (0..1u32 << s_box.input_bits()).collect();
+        self.ddt_rows(s_box, &differences)
+    }
+
+    pub fn differential_uniformity(&self, s_box: &SBox) -> u32 {
+        let differences: Vec<u32> = (1..1u32 << s_box.input_bits()).collect();
+        differences
+            .chunks(DDT_ROWS_PER_DISPATCH)
+            .flat_map(|chunk| self.ddt_rows(s_box, chunk))
+            .flat_map(|row| row.into_iter().max())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn encrypt_many(&self, spn: &Spn, blocks: &[u32]) -> Result<Vec<u32>, &'static str> {
+        spn.check_word_size()?;
+
+        Ok(self
+            .spn_on_gpu(spn)
+            .and_then(|gpu| gpu.encrypt_many(spn, blocks))
+            .unwrap_or_else(|| {
+                blocks
+                    .iter()
+                    .map(|&x| bits2num(&spn.encrypt(&num2bits(x, spn.block_size()))))
+                    .collect()
+            }))
+    }
+
+    pub fn codebook(&self, spn: &Spn) -> Result<SBox, &'static str> {
+        let n = spn.block_size();
+        if n > MAX_CODEBOOK_BITS {
+            return Err("block size is too large for a codebook");
+        }
+
+        let blocks: Vec<u32> = (0..1u32 << n).collect();
+        SBox::from_flat(self.encrypt_many(spn, &blocks)?, n, n)
+    }
+
+    pub fn avalanche_matrix(
+        &self,
+        spn: &Spn,
+        rounds: usize,
+        samples: usize,
+        mut random: impl FnMut() -> u32,
+    ) -> Result<Vec<Vec<f64>>, &'static str> {
+        if samples == 0 {
+            return Err("no samples");
+        }
+
+        let reduced = match spn.reduced(rounds) {
+            Ok(reduced) if self.spn_on_gpu(spn).is_some() => reduced,
+            _ => return spn.avalanche_matrix(rounds, samples, random),
+        };
+
+        let n = spn.block_size();
+        let mask = (u64::MAX >> (64 - n)) as u32;
+        let blocks: Vec<u32> = (0..samples)
+            .flat_map(|_| {
+                let x = random() & mask;
+                (0..=n).map(move |i| match i {
+                    0 => x,
+                    _ => x ^ (1 << (n - i)),
+                })
+            })
+            .collect();
+        let encrypted = self.encrypt_many(&reduced, &blocks)?;
+
+        let mut counts = vec![vec![0usize; n]; n];
+        for sample in encrypted.chunks(n + 1) {
+            for (row, &y) in counts.iter_mut().zip(&sample[1..]) {
+                let difference = sample[0] ^ y;
+                for (j, count) in row.iter_mut().enumerate() {
+                    *count += ((difference >> (n - 1 - j)) & 1) as usize;
+                }
+            }
+        }
+
+        Ok(counts
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| c as f64 / samples as f64).collect())
+            .collect())
+    }
+}
+
+impl Default for Accelerator {
+    fn default() -> Self {
+        Accelerator::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_spn;
+
+    #[test]
+    fn test_accelerator() {
+        let spn = heys_spn();
+        for accelerator in [Accelerator::new(), Accelerator::cpu()] {
+            assert!(accelerator.ddt(spn.s_box()) == spn.s_box().ddt());
+            assert_eq!(
+                accelerator.differential_uniformity(spn.s_box()),
+                spn.s_box().differential_uniformity()
+            );
+
+            let codebook = accelerator.codebook(&spn).unwrap();
+            assert_eq!(
+                codebook.lookup(0x26b7),
+                bits2num(&spn.encrypt(&num2bits(0x26b7, 16)))
+            );
+
+            let random = |mut seed: u32| {
+                move || {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    seed >> 8
+                }
+            };
+            assert_eq!(
+                accelerator.avalanche_matrix(&spn, 2, 64, random(0x1234)),
+                spn.avalanche_matrix(2, 64, random(0x1234))
+            );
+            assert_eq!(
+                accelerator.avalanche_matrix(&spn, 2, 0, random(0x1234)),
+                Err("no samples")
+            );
+        }
+
+        let wide = SBox::from_fn(16, 16, |x| (x.wrapping_mul(0x9e37) ^ 0x5a5a) & 0xffff).unwrap();
+        let rows = Accelerator::new().ddt_rows(&wide, &[0x0001, 0x8000]);
+        assert_eq!(rows, vec![wide.ddt_row(0x0001), wide.ddt_row(0x8000)]);
+        let present = Spn::present80(0).unwrap();
+        assert!(Accelerator::cpu().codebook(&present).is_err());
+        assert!(Accelerator::cpu().encrypt_many(&present, &[0]).is_err());
+        assert!(Accelerator::cpu()
+            .avalanche_matrix(&present, 1, 1, || 0)
+            .is_err());
+    }
+}
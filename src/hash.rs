@@ -0,0 +1,93 @@
+use crate::block_cipher::xor;
+use crate::BlockCipher;
+
+pub fn davies_meyer<C: BlockCipher>(
+    cipher: impl Fn(&[bool]) -> C,
+    chaining: &[bool],
+    block: &[bool],
+) -> Vec<bool> {
+    xor(&cipher(block).encrypt_block(chaining), chaining)
+}
+
+pub fn matyas_meyer_oseas<C: BlockCipher>(
+    cipher: impl Fn(&[bool]) -> C,
+    chaining: &[bool],
+    block: &[bool],
+) -> Vec<bool> {
+    xor(&cipher(chaining).encrypt_block(block), block)
+}
+
+pub fn merkle_damgard(
+    compress: impl Fn(&[bool], &[bool]) -> Vec<bool>,
+    iv: &[bool],
+    block_size: usize,
+    message: &[u8],
+) -> Result<Vec<bool>, &'static str> {
+    if block_size == 0 {
+        return Err("block size must be positive");
+    }
+
+    let mut bits: Vec<bool> = message
+        .iter()
+        .flat_map(|&byte| (0..8).map(move |i| (byte >> (7 - i)) & 1 == 1))
+        .collect();
+    let length = bits.len() as u64;
+
+    bits.push(true);
+    while !bits.len().is_multiple_of(block_size) {
+        bits.push(false);
+    }
+    bits.extend((0..block_size).map(|i| {
+        let shift = block_size - 1 - i;
+        shift < 64 && (length >> shift) & 1 == 1
+    }));
+
+    bits.chunks(block_size)
+        .try_fold(iv.to_vec(), |chaining, block| {
+            let next = compress(&chaining, block);
+            if next.len() != iv.len() {
+                return Err("compressor output does not match iv length");
+            }
+
+            Ok(next)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits, MiniAes};
+
+    fn mini_aes(key: &[bool]) -> MiniAes {
+        MiniAes::new(bits2num(key)).unwrap()
+    }
+
+    #[test]
+    fn test_compression_functions() {
+        let (h, m) = (num2bits(0x9c63, 16), num2bits(0xc3f0, 16));
+        assert_eq!(bits2num(&davies_meyer(mini_aes, &h, &m)), 0x72c6 ^ 0x9c63);
+        assert_eq!(
+            bits2num(&matyas_meyer_oseas(mini_aes, &m, &h)),
+            0x72c6 ^ 0x9c63
+        );
+    }
+
+    #[test]
+    fn test_merkle_damgard() {
+        let iv = num2bits(0x0123, 16);
+        let compress = |h: &[bool], m: &[bool]| davies_meyer(mini_aes, h, m);
+
+        let first = compress(&iv, &num2bits(0x6162, 16));
+        let second = compress(&first, &num2bits(0x8000, 16));
+        let expected = compress(&second, &num2bits(16, 16));
+        assert_eq!(merkle_damgard(compress, &iv, 16, b"ab"), Ok(expected));
+
+        assert_ne!(
+            merkle_damgard(compress, &iv, 16, b""),
+            merkle_damgard(compress, &iv, 16, b"\0")
+        );
+        assert!(merkle_damgard(compress, &iv, 0, b"ab").is_err());
+        let keep_block = |_: &[bool], m: &[bool]| m.to_vec();
+        assert!(merkle_damgard(keep_block, &num2bits(0x01, 8), 16, b"ab").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{num2bits, PBox, SBox};
+
+fn vhdl_bits(num: u32, bit_count: usize) -> String {
+    let bits: String = num2bits(num, bit_count)
+        .iter()
+        .map(|&bit| if bit { '1' } else { '0' })
+        .collect();
+
+    format!("\"{}\"", bits)
+}
+
+fn vhdl_entity(name: &str, input_bits: usize, output_bits: usize) -> String {
+    format!(
+        "library ieee;\nuse ieee.std_logic_1164.all;\n\n\
+         entity {name} is\n    port (\n        \
+         x : in  std_logic_vector({} downto 0);\n        \
+         y : out std_logic_vector({} downto 0)\n    );\nend entity;\n\n",
+        input_bits - 1,
+        output_bits - 1,
+    )
+}
+
+impl SBox {
+    pub fn to_verilog(&self, name: &str) -> String {
+        let (n, m) = (self.input_bits, self.output_bits);
+
+        let mut result = format!(
+            "module {name} (\n    input  wire [{}:0] x,\n    output reg  [{}:0] y\n);\n",
+            n - 1,
+            m - 1,
+        );
+        result.push_str("    always @(*) begin\n        case (x)\n");
+        for (x, &y) in self.table.iter().enumerate() {
+            result.push_str(&format!(
+                "            {n}'h{:0nd$x}: y = {m}'h{:0md$x};\n",
+                x,
+                y,
+                nd = n.div_ceil(4),
+                md = m.div_ceil(4),
+            ));
+        }
+        result.push_str(&format!(
+            "            default: y = {m}'h0;\n        endcase\n    end\nendmodule\n"
+        ));
+
+        result
+    }
+
+    pub fn to_vhdl(&self, name: &str) -> String {
+        let (n, m) = (self.input_bits, self.output_bits);
+
+        let mut result = vhdl_entity(name, n, m);
+        result.push_str(&format!(
+            "architecture rom of {name} is\nbegin\n    process (x)\n    begin\n        case x is\n"
+        ));
+        for (x, &y) in self.table.iter().enumerate() {
+            result.push_str(&format!(
+                "            when {} => y <= {};\n",
+                vhdl_bits(x as u32, n),
+                vhdl_bits(y, m)
+            ));
+        }
+        result.push_str(
+            "            when others => y <= (others => '0');\n        end case;\n    \
+             end process;\nend architecture;\n",
+        );
+
+        result
+    }
+}
+
+impl PBox {
+    pub fn to_verilog(&self, name: &str) -> Result<String, &'static str> {
+        let n = self.permutation.len();
+        if n == 0 {
+            return Err("empty permutation");
+        }
+
+        let mut result = format!(
+            "module {name} (\n    input  wire [{}:0] x,\n    output wire [{}:0] y\n);\n",
+            n - 1,
+            n - 1,
+        );
+        for (i, &num) in self.permutation.iter().enumerate() {
+            result.push_str(&format!(
+                "    assign y[{}] = x[{}];\n",
+                n - num as usize,
+                n - 1 - i
+            ));
+        }
+        result.push_str("endmodule\n");
+
+        Ok(result)
+    }
+
+    pub fn to_vhdl(&self, name: &str) -> Result<String, &'static str> {
+        let n = self.permutation.len();
+        if n == 0 {
+            return Err("empty permutation");
+        }
+
+        let mut result = vhdl_entity(name, n, n);
+        result.push_str(&format!("architecture wiring of {name} is\nbegin\n"));
+        for (i, &num) in self.permutation.iter().enumerate() {
+            result.push_str(&format!(
+                "    y({}) <= x({});\n",
+                n - num as usize,
+                n - 1 - i
+            ));
+        }
+        result.push_str("end architecture;\n");
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verilog() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        assert_eq!(
+            s_box.to_verilog("s"),
+            "module s (\n    input  wire [1:0] x,\n    output reg  [1:0] y\n);\n    \
+             always @(*) begin\n        case (x)\n            \
+             2'h0: y = 2'h2;\n            2'h1: y = 2'h0;\n            \
+             2'h2: y = 2'h3;\n            2'h3: y = 2'h1;\n            \
+             default: y = 2'h0;\n        endcase\n    end\nendmodule\n"
+        );
+
+        let p_box = PBox::new(vec![2, 3, 1]).unwrap();
+        assert_eq!(
+            p_box.to_verilog("p").unwrap(),
+            "module p (\n    input  wire [2:0] x,\n    output wire [2:0] y\n);\n    \
+             assign y[1] = x[2];\n    assign y[0] = x[1];\n    assign y[2] = x[0];\n\
+             endmodule\n"
+        );
+        assert!(PBox::new(vec![]).unwrap().to_verilog("p").is_err());
+    }
+
+    #[test]
+    fn test_vhdl() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let vhdl = s_box.to_vhdl("s");
+        assert!(vhdl.contains("x : in  std_logic_vector(1 downto 0);"));
+        assert!(vhdl.contains("            when \"10\" => y <= \"11\";\n"));
+
+        let p_box = PBox::new(vec![2, 3, 1]).unwrap();
+        assert!(p_box.to_vhdl("p").unwrap().ends_with(
+            "begin\n    y(1) <= x(2);\n    y(0) <= x(1);\n    y(2) <= x(0);\nend architecture;\n"
+        ));
+        assert!(PBox::new(vec![]).unwrap().to_vhdl("p").is_err());
+    }
+}
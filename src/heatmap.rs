@@ -0,0 +1,51 @@
+use image::{Rgb, RgbImage};
+
+fn color(value: f64) -> Rgb<u8> {
+    let channel = |offset: f64| ((3.0 * value - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgb([channel(0.0), channel(1.0), channel(2.0)])
+}
+
+pub fn heatmap<T: Copy + Into<i64>>(table: &[Vec<T>], scale: u32) -> RgbImage {
+    let rows = table.len() as u32;
+    let columns = table.first().map_or(0, Vec::len) as u32;
+
+    let max = table
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(j, &el)| (i, j, Into::<i64>::into(el)))
+        })
+        .filter(|&(i, j, _)| i != 0 || j != 0)
+        .map(|(_, _, el)| el.abs())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    RgbImage::from_fn(columns * scale, rows * scale, |x, y| {
+        let el: i64 = table[(y / scale) as usize][(x / scale) as usize].into();
+        color((el.abs() as f64 / max as f64).min(1.0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SBox;
+
+    #[test]
+    fn test_heatmap() {
+        let s_box = SBox::new(vec![vec![0, 3], vec![1, 2]]).unwrap();
+        let image = heatmap(&s_box.lat(), 4);
+        assert_eq!(image.dimensions(), (16, 16));
+        assert_eq!(*image.get_pixel(0, 0), Rgb([255, 255, 255]));
+        assert_eq!(*image.get_pixel(4, 0), Rgb([0, 0, 0]));
+
+        let mut png = Vec::new();
+        image
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+}
@@ -0,0 +1,199 @@
+use crate::Spn;
+
+pub struct ImpossibleDifferential {
+    pub input: u32,
+    pub output: u32,
+    pub rounds: usize,
+    pub forward_rounds: usize,
+}
+
+#[derive(Clone)]
+struct Truncated {
+    zero: u32,
+    nonzero: Vec<u32>,
+}
+
+impl Truncated {
+    fn from_difference(difference: u32, block_size: usize) -> Truncated {
+        let full = (u64::MAX >> (64 - block_size)) as u32;
+        Truncated {
+            zero: !difference & full,
+            nonzero: (0..block_size)
+                .map(|i| 1 << i)
+                .filter(|&bit| difference & bit != 0)
+                .collect(),
+        }
+    }
+
+    fn contradicts(&self, other: &Truncated) -> bool {
+        self.nonzero.iter().any(|&mask| mask & !other.zero == 0)
+            || other.nonzero.iter().any(|&mask| mask & !self.zero == 0)
+    }
+}
+
+impl Spn {
+    fn chunk_mask(&self, index: usize) -> u32 {
+        self.with_chunk(0, index, (1 << self.s_box().input_bits()) - 1)
+    }
+
+    fn substitute_truncated(&self, state: &Truncated) -> Truncated {
+        let chunks: Vec<u32> = (0..self.s_box_count())
+            .map(|j| self.chunk_mask(j))
+            .collect();
+
+        let zero = chunks
+            .iter()
+            .filter(|&&chunk| chunk & !state.zero == 0)
+            .fold(0, |acc, &chunk| acc | chunk);
+        let mut nonzero: Vec<u32> = state
+            .nonzero
+            .iter()
+            .map(|&mask| {
+                chunks
+                    .iter()
+                    .filter(|&&chunk| chunk & mask != 0)
+                    .fold(0, |acc, &chunk| acc | chunk)
+                    & !zero
+            })
+            .collect();
+        nonzero.sort_unstable();
+        nonzero.dedup();
+
+        Truncated { zero, nonzero }
+    }
+
+    fn permute_truncated(&self, state: &Truncated, inverse: bool) -> Truncated {
+        let permute = |value| match inverse {
+            false => self.p_box().encrypt_value(value),
+            true => self.p_box().decrypt_value(value),
+        };
+
+        Truncated {
+            zero: permute(state.zero),
+            nonzero: state.nonzero.iter().map(|&mask| permute(mask)).collect(),
+        }
+    }
+
+    pub fn impossible_differential_split(
+        &self,
+        input: u32,
+        output: u32,
+        rounds: usize,
+    ) -> Result<Option<usize>, &'static str> {
+        self.check_word_size()?;
+        if self.s_box().inverse().is_err() {
+            return Err("s-box is not bijective");
+        }
+
+        let n = self.block_size();
+
+        let mut forward = vec![Truncated::from_difference(input, n)];
+        for _ in 0..rounds {
+            let state = self.substitute_truncated(forward.last().unwrap());
+            forward.push(self.permute_truncated(&state, false));
+        }
+
+        let mut backward = Truncated::from_difference(output, n);
+        for round in (0..=rounds).rev() {
+            if forward[round].contradicts(&backward) {
+                return Ok(Some(round));
+            }
+            if round > 0 {
+                let state = self.permute_truncated(&backward, true);
+                backward = self.substitute_truncated(&state);
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn impossible_differentials(
+        &self,
+        rounds: usize,
+    ) -> Result<Vec<ImpossibleDifferential>, &'static str> {
+        self.check_word_size()?;
+        if self.s_box().inverse().is_err() {
+            return Err("s-box is not bijective");
+        }
+
+        let differences: Vec<u32> = (0..self.s_box_count())
+            .flat_map(|j| (1..1 << self.s_box().input_bits()).map(move |a| (j, a)))
+            .map(|(j, a)| self.with_chunk(0, j, a))
+            .collect();
+
+        let mut result = Vec::new();
+        for &input in &differences {
+            for &output in &differences {
+                if let Some(forward_rounds) =
+                    self.impossible_differential_split(input, output, rounds)?
+                {
+                    result.push(ImpossibleDifferential {
+                        input,
+                        output,
+                        rounds,
+                        forward_rounds,
+                    });
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+    use crate::{bits2num, num2bits, PBox, SBox};
+
+    #[test]
+    fn test_impossible_differentials() {
+        let s_box = heys_s_box();
+        let p_box = PBox::new(vec![1, 5, 2, 6, 3, 7, 4, 8]).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 8); 4]).unwrap();
+
+        assert!(!spn.impossible_differentials(1).unwrap().is_empty());
+        for rounds in 1..=3 {
+            let found = spn.impossible_differentials(rounds).unwrap();
+
+            for differential in found {
+                for x in 0..1 << 8 {
+                    let (mut a, mut b) = (num2bits(x, 8), num2bits(x ^ differential.input, 8));
+                    for round in 0..rounds {
+                        a = spn.encrypt_round(&a, round);
+                        b = spn.encrypt_round(&b, round);
+                    }
+                    assert_ne!(bits2num(&a) ^ bits2num(&b), differential.output);
+                }
+            }
+        }
+        assert_eq!(
+            spn.impossible_differential_split(0x10, 0x10, 1),
+            Ok(Some(1))
+        );
+    }
+
+    #[test]
+    fn test_present_is_rejected() {
+        let spn = Spn::present80(0).unwrap();
+        assert!(spn.impossible_differentials(1).is_err());
+        assert!(spn.impossible_differential_split(1, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_non_bijective_is_rejected() {
+        let s_box = SBox::from_flat(vec![3, 0, 3, 1], 2, 2).unwrap();
+        let p_box = PBox::new(vec![1, 3, 2, 4]).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 4); 3]).unwrap();
+
+        assert_eq!(
+            spn.impossible_differentials(2).err(),
+            Some("s-box is not bijective")
+        );
+        assert_eq!(
+            spn.impossible_differential_split(1, 1, 2),
+            Err("s-box is not bijective")
+        );
+    }
+}
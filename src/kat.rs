@@ -0,0 +1,300 @@
+use crate::{bits2num, num2bits, BlockCipher, Spn};
+
+#[derive(Default)]
+pub struct KatVector {
+    pub count: usize,
+    pub round_keys: Vec<u32>,
+    pub plaintext: u32,
+    pub ciphertext: u32,
+    pub rounds: Vec<(usize, u32)>,
+}
+
+pub struct KatMismatch {
+    pub count: usize,
+    pub expected: u32,
+    pub actual: u32,
+    pub trace: Vec<(usize, u32, u32)>,
+}
+
+fn parse_hex(value: &str) -> Result<u32, &'static str> {
+    u32::from_str_radix(value.trim_start_matches("0x"), 16).map_err(|_| "invalid hex value")
+}
+
+pub fn parse_kat(text: &str) -> Result<Vec<KatVector>, &'static str> {
+    let mut vectors: Vec<KatVector> = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (name, value) = line.split_once('=').ok_or("expected name = value")?;
+        let (name, value) = (name.trim().to_uppercase(), value.trim());
+        if name == "COUNT" {
+            vectors.push(KatVector {
+                count: value.parse().map_err(|_| "invalid count")?,
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let vector = vectors.last_mut().ok_or("value before first COUNT")?;
+        match name.as_str() {
+            "KEY" => {
+                vector.round_keys = value
+                    .split_whitespace()
+                    .map(parse_hex)
+                    .collect::<Result<_, _>>()?
+            }
+            "PLAINTEXT" => vector.plaintext = parse_hex(value)?,
+            "CIPHERTEXT" => vector.ciphertext = parse_hex(value)?,
+            _ => {
+                let round = name
+                    .strip_prefix("ROUND")
+                    .and_then(|round| round.parse().ok())
+                    .ok_or("unknown field")?;
+                vector.rounds.push((round, parse_hex(value)?));
+            }
+        }
+    }
+
+    Ok(vectors)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RspVector {
+    pub section: String,
+    pub count: usize,
+    pub key: Vec<bool>,
+    pub plaintext: Vec<bool>,
+    pub ciphertext: Vec<bool>,
+}
+
+impl RspVector {
+    pub fn is_decrypt(&self) -> bool {
+        self.section == "DECRYPT"
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RspMismatch {
+    pub section: String,
+    pub count: usize,
+    pub expected: Vec<bool>,
+    pub actual: Vec<bool>,
+}
+
+fn parse_hex_bits(value: &str) -> Result<Vec<bool>, &'static str> {
+    value
+        .chars()
+        .map(|c| c.to_digit(16).ok_or("invalid hex value"))
+        .map(|digit| Ok(num2bits(digit?, 4)))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|digits| digits.concat())
+}
+
+pub fn parse_rsp(text: &str) -> Result<Vec<RspVector>, &'static str> {
+    let mut vectors: Vec<RspVector> = Vec::new();
+    let mut section = String::from("ENCRYPT");
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            if !name.contains('=') {
+                section = name.trim().to_uppercase();
+            }
+            continue;
+        }
+
+        let (name, value) = line.split_once('=').ok_or("expected name = value")?;
+        let (name, value) = (name.trim().to_uppercase(), value.trim());
+        if name == "COUNT" {
+            vectors.push(RspVector {
+                section: section.clone(),
+                count: value.parse().map_err(|_| "invalid count")?,
+                key: Vec::new(),
+                plaintext: Vec::new(),
+                ciphertext: Vec::new(),
+            });
+            continue;
+        }
+
+        let vector = vectors.last_mut().ok_or("value before first COUNT")?;
+        match name.as_str() {
+            "KEY" => vector.key = parse_hex_bits(value)?,
+            "PLAINTEXT" => vector.plaintext = parse_hex_bits(value)?,
+            "CIPHERTEXT" => vector.ciphertext = parse_hex_bits(value)?,
+            _ => {}
+        }
+    }
+
+    Ok(vectors)
+}
+
+pub fn run_rsp<C: BlockCipher>(
+    cipher: impl Fn(&[bool]) -> Result<C, &'static str>,
+    vectors: &[RspVector],
+) -> Result<Vec<RspMismatch>, &'static str> {
+    let mut mismatches = Vec::new();
+    for vector in vectors {
+        let cipher = cipher(&vector.key)?;
+        let (input, expected) = match vector.is_decrypt() {
+            true => (&vector.ciphertext, &vector.plaintext),
+            false => (&vector.plaintext, &vector.ciphertext),
+        };
+        if input.len() != cipher.block_size() || expected.len() != cipher.block_size() {
+            return Err("vector does not match block size");
+        }
+
+        let actual = match vector.is_decrypt() {
+            true => cipher.decrypt_block(input),
+            false => cipher.encrypt_block(input),
+        };
+        if actual != *expected {
+            mismatches.push(RspMismatch {
+                section: vector.section.clone(),
+                count: vector.count,
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
+impl Spn {
+    pub fn trace(&self, bits: &[bool]) -> Vec<Vec<bool>> {
+        let mut states = Vec::new();
+        let mut state = bits.to_vec();
+        for round in 0..self.rounds() {
+            state = self.encrypt_round(&state, round);
+            states.push(state.clone());
+        }
+        states.push(self.encrypt(bits));
+
+        states
+    }
+
+    fn with_round_keys(&self, round_keys: &[u32]) -> Result<Spn, &'static str> {
+        let round_keys = round_keys
+            .iter()
+            .map(|&key| num2bits(key, self.block_size()))
+            .collect();
+
+        Spn::new(self.s_box().clone(), self.p_box().clone(), round_keys)
+    }
+
+    pub fn run_kat(&self, vectors: &[KatVector]) -> Result<Vec<KatMismatch>, &'static str> {
+        self.check_word_size()?;
+
+        let mut mismatches = Vec::new();
+        for vector in vectors {
+            let keyed;
+            let spn = match vector.round_keys.is_empty() {
+                true => self,
+                false => {
+                    keyed = self.with_round_keys(&vector.round_keys)?;
+                    &keyed
+                }
+            };
+
+            let states: Vec<u32> = spn
+                .trace(&num2bits(vector.plaintext, self.block_size()))
+                .iter()
+                .map(|state| bits2num(state))
+                .collect();
+            let actual = *states.last().unwrap();
+
+            let trace: Vec<(usize, u32, u32)> = vector
+                .rounds
+                .iter()
+                .filter(|&&(round, _)| round >= 1 && round <= spn.rounds())
+                .map(|&(round, expected)| (round, expected, states[round - 1]))
+                .filter(|&(_, expected, actual)| expected != actual)
+                .collect();
+
+            if actual != vector.ciphertext || !trace.is_empty() {
+                mismatches.push(KatMismatch {
+                    count: vector.count,
+                    expected: vector.ciphertext,
+                    actual,
+                    trace,
+                });
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_spn;
+
+    #[test]
+    fn test_kat() {
+        let spn = heys_spn();
+        let plaintext = num2bits(0x26b7, 16);
+        let states: Vec<u32> = spn.trace(&plaintext).iter().map(|s| bits2num(s)).collect();
+        assert_eq!(states.len(), 5);
+
+        let text = format!(
+            "# heys\nCOUNT = 0\nPLAINTEXT = 26b7\nROUND1 = {:04x}\nCIPHERTEXT = {:04x}\n\n\
+             COUNT = 1\nKEY = 0 0 0 0 0\nPLAINTEXT = 26b7\nCIPHERTEXT = {:04x}\n\n\
+             COUNT = 2\nPLAINTEXT = 26b7\nROUND2 = 0\nCIPHERTEXT = 0\n",
+            states[0], states[4], states[4]
+        );
+        let vectors = parse_kat(&text).unwrap();
+        assert_eq!(vectors.len(), 3);
+
+        let mismatches = spn.run_kat(&vectors).unwrap();
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].count, 1);
+        assert_eq!(mismatches[1].trace, vec![(2, 0, states[1])]);
+
+        assert!(parse_kat("PLAINTEXT = 0\n").is_err());
+        assert!(parse_kat("COUNT = 0\nNONCE = 1\n").is_err());
+        assert!(Spn::present80(0).unwrap().run_kat(&vectors).is_err());
+    }
+
+    #[test]
+    fn test_rsp() {
+        let text = "# CAVS-style PRESENT-80 vectors\n\
+                    [ENCRYPT]\n\n\
+                    COUNT = 0\nKEY = 00000000000000000000\n\
+                    PLAINTEXT = 0000000000000000\nCIPHERTEXT = 5579c1387b228445\n\n\
+                    COUNT = 1\nKEY = ffffffffffffffffffff\n\
+                    PLAINTEXT = 0000000000000000\nCIPHERTEXT = e72c46c0f5945049\n\n\
+                    [DECRYPT]\n\n\
+                    COUNT = 0\nKEY = 00000000000000000000\nIV = 00\n\
+                    CIPHERTEXT = a112ffc72f68417b\nPLAINTEXT = ffffffffffffffff\n\n\
+                    COUNT = 1\nKEY = ffffffffffffffffffff\n\
+                    CIPHERTEXT = 3333dcd3213210d2\nPLAINTEXT = 0000000000000000\n";
+        let vectors = parse_rsp(text).unwrap();
+        assert_eq!(vectors.len(), 4);
+        assert!(vectors[2].is_decrypt());
+        assert_eq!(vectors[0].key.len(), 80);
+
+        let present = |key: &[bool]| {
+            Spn::present80(key.iter().fold(0u128, |acc, &bit| (acc << 1) | bit as u128))
+        };
+        let mismatches = run_rsp(present, &vectors).unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].section, "DECRYPT");
+        assert_eq!(mismatches[0].count, 1);
+        assert_eq!(
+            mismatches[0].actual,
+            parse_hex_bits("ffffffffffffffff").unwrap()
+        );
+
+        assert!(parse_rsp("COUNT = 0\nKEY = xyz\n").is_err());
+    }
+}
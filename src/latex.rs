@@ -0,0 +1,89 @@
+use crate::SBox;
+
+#[derive(Default)]
+pub struct LatexOptions {
+    pub hex: bool,
+    pub highlight_max: bool,
+}
+
+fn format_entry(value: i64, hex: bool) -> String {
+    match (hex, value < 0) {
+        (false, _) => value.to_string(),
+        (true, false) => format!("{:x}", value),
+        (true, true) => format!("-{:x}", -value),
+    }
+}
+
+pub fn latex_table<T: Copy + Into<i64>>(table: &[Vec<T>], options: &LatexOptions) -> String {
+    let columns = table.first().map_or(0, Vec::len);
+
+    let max = table
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(j, &el)| (i, j, Into::<i64>::into(el)))
+        })
+        .filter(|&(i, j, _)| i != 0 || j != 0)
+        .map(|(_, _, el)| el.abs())
+        .max();
+
+    let mut result = format!("\\begin{{tabular}}{{c|{}}}\n", "c".repeat(columns));
+    for j in 0..columns {
+        result.push_str(&format!(" & {:x}", j));
+    }
+    result.push_str(" \\\\\n\\hline\n");
+
+    for (i, row) in table.iter().enumerate() {
+        result.push_str(&format!("{:x}", i));
+        for &el in row {
+            let el: i64 = el.into();
+            let entry = format_entry(el, options.hex);
+            if options.highlight_max && Some(el.abs()) == max {
+                result.push_str(&format!(" & \\textbf{{{}}}", entry));
+            } else {
+                result.push_str(&format!(" & {}", entry));
+            }
+        }
+        result.push_str(" \\\\\n");
+    }
+    result.push_str("\\end{tabular}\n");
+
+    result
+}
+
+impl SBox {
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        let columns = 1 << (self.input_bits - self.input_bits / 2);
+        let rows: Vec<Vec<u32>> = self.table.chunks(columns).map(<[u32]>::to_vec).collect();
+
+        latex_table(&rows, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latex() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let options = LatexOptions {
+            hex: true,
+            highlight_max: false,
+        };
+        assert_eq!(
+            s_box.to_latex(&options),
+            "\\begin{tabular}{c|cc}\n & 0 & 1 \\\\\n\\hline\n\
+             0 & 2 & 0 \\\\\n1 & 3 & 1 \\\\\n\\end{tabular}\n"
+        );
+
+        let lat = vec![vec![2, 0], vec![0, -2]];
+        let options = LatexOptions {
+            hex: false,
+            highlight_max: true,
+        };
+        assert!(latex_table(&lat, &options).contains("1 & 0 & \\textbf{-2} \\\\\n"));
+    }
+}
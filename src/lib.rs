@@ -1,3 +1,134 @@
+#![cfg_attr(portable_simd, feature(portable_simd))]
+
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+use std::sync::OnceLock;
+
+mod analysis;
+mod arx;
+mod attack;
+mod avalanche;
+mod batch;
+mod binary;
+mod block_cipher;
+mod boolean;
+mod boomerang;
+mod borrowed;
+mod builder;
+mod c_array;
+mod ciphers;
+mod circuit;
+mod cnf;
+mod codebook;
+mod codegen;
+#[cfg(feature = "config")]
+mod config;
+mod constant_time;
+mod convert;
+mod csv;
+mod ctr;
+mod display;
+mod division;
+mod dot;
+mod equivalence;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod fixed;
+#[cfg(test)]
+mod fixtures;
+mod fpe;
+mod fuzz;
+#[cfg(feature = "gpu")]
+mod gpu;
+mod hash;
+mod hdl;
+#[cfg(feature = "image")]
+mod heatmap;
+mod impossible;
+mod kat;
+mod latex;
+mod mac;
+mod macros;
+mod masking;
+mod milp;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "ndarray")]
+mod ndarray_impls;
+mod npy;
+mod permutation;
+mod pla;
+#[cfg(feature = "python")]
+mod python;
+mod randomness;
+mod reference;
+mod report;
+mod sage;
+mod sboxu;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(portable_simd)]
+mod simd;
+mod spn;
+#[cfg(feature = "test-strategies")]
+mod strategies;
+mod svg;
+mod table;
+mod trail;
+mod truncated;
+mod verify;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weak_keys;
+
+pub use arx::{AddMod2n, RxLayer};
+pub use attack::KeyRecovery;
+pub use avalanche::dependence_matrix;
+pub use block_cipher::BlockCipher;
+pub use boolean::BooleanFunction;
+pub use boomerang::Boomerang;
+pub use borrowed::SBoxRef;
+pub use builder::{BitOrder, Indexing, PBoxBuilder, SBoxBuilder};
+pub use ciphers::{MiniAes, SDes};
+pub use circuit::{Circuit, Gate, GateOp};
+pub use cnf::Cnf;
+pub use codebook::codebook;
+pub use codegen::CHeader;
+#[cfg(feature = "config")]
+pub use config::FileFormat;
+pub use constant_time::ConstantTimeSBox;
+pub use csv::{CsvOptions, Radix};
+pub use ctr::{ctr_encrypt, CtrKeystream};
+pub use equivalence::PermutationEquivalence;
+pub use fixed::{ConstSBox, PBoxN, SBoxN};
+pub use fpe::Ffx;
+pub use fuzz::{fuzz_p_box, fuzz_parsers, fuzz_s_box, fuzz_spn};
+#[cfg(feature = "gpu")]
+pub use gpu::Accelerator;
+pub use hash::{davies_meyer, matyas_meyer_oseas, merkle_damgard};
+#[cfg(feature = "image")]
+pub use heatmap::heatmap;
+pub use impossible::ImpossibleDifferential;
+pub use kat::{parse_kat, parse_rsp, run_rsp, KatMismatch, KatVector, RspMismatch, RspVector};
+pub use latex::{latex_table, LatexOptions};
+pub use mac::{cbc_mac, cmac, cmac_subkeys};
+#[cfg(feature = "mmap")]
+pub use mmap::{write_codebook, write_table, MmapSBox};
+pub use npy::npy;
+pub use randomness::{monobit_test, poker_test, runs_test, serial_test};
+pub use reference::{reference_s_boxes, RankEntry};
+pub use report::{SBoxComparison, SBoxReport};
+pub use spn::Spn;
+#[cfg(feature = "test-strategies")]
+pub use strategies::{p_box_strategy, s_box_strategy, spn_strategy};
+pub use table::AnalysisTable;
+pub use trail::{DifferentialTrail, LinearTrail};
+pub use verify::Finding;
+pub use weak_keys::{screen_round_keys, KeyDiagnostics, WeakKeyCriteria, WeakKeyIssue};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 pub fn bits2num(bits: &[bool]) -> u32 {
     let mut result = 0;
 
@@ -20,9 +151,12 @@ pub fn num2bits(num: u32, bit_count: usize) -> Vec<bool> {
     result
 }
 
+#[derive(Clone)]
 pub struct SBox {
-    table: Vec<Vec<u32>>,
-    inverse_table: Vec<Vec<u32>>,
+    table: Vec<u32>,
+    inverse_table: OnceLock<Vec<u32>>,
+    input_bits: usize,
+    output_bits: usize,
 }
 
 impl SBox {
@@ -31,12 +165,67 @@ impl SBox {
             return Err("invalid table");
         }
 
+        let input_bits = Self::ceil_log(table.len()) + Self::ceil_log(table[0].len());
+        let output_bits = Self::max_bits(&table);
+
+        let table = match table.len() {
+            1 => table.into_iter().next().unwrap(),
+            _ => table.into_iter().flatten().collect(),
+        };
+
+        Ok(SBox {
+            table,
+            inverse_table: OnceLock::new(),
+            input_bits,
+            output_bits,
+        })
+    }
+
+    pub fn from_flat(
+        table: Vec<u32>,
+        input_bits: usize,
+        output_bits: usize,
+    ) -> Result<SBox, &'static str> {
+        if input_bits >= 32 || table.len() != 1 << input_bits {
+            return Err("table size does not match input bits");
+        }
+
+        let bits = table
+            .iter()
+            .map(|&el| (u32::BITS - el.leading_zeros()) as usize)
+            .max()
+            .unwrap_or(0);
+        if bits != input_bits {
+            return Err("invalid table");
+        }
+        if bits != output_bits {
+            return Err("table values do not match output bits");
+        }
+
         Ok(SBox {
-            inverse_table: Self::reverse_table(&table),
             table,
+            inverse_table: OnceLock::new(),
+            input_bits,
+            output_bits,
         })
     }
 
+    pub fn from_fn(
+        input_bits: usize,
+        output_bits: usize,
+        f: impl FnMut(u32) -> u32,
+    ) -> Result<SBox, &'static str> {
+        if input_bits >= 32 {
+            return Err("table size does not match input bits");
+        }
+
+        SBox::from_flat(
+            (0..1u32 << input_bits).map(f).collect(),
+            input_bits,
+            output_bits,
+        )
+    }
+
     fn check_table(table: &Vec<Vec<u32>>) -> bool {
         let n = table.len();
         if (n == 0) || n != (1 << Self::ceil_log(n)) {
@@ -53,12 +242,11 @@ impl SBox {
             }
         }
 
-        let result_bits_count = Self::max_bits(&table);
+        let result_bits_count = Self::max_bits(table);
         if result_bits_count != Self::ceil_log(n) + Self::ceil_log(m) {
             return false;
         }
 
-
         true
     }
 
@@ -66,7 +254,8 @@ impl SBox {
         let mut result_bits_count = 0;
         for row in table {
             for &el in row {
-                result_bits_count = std::cmp::max(result_bits_count, Self::ceil_log(el as usize));
+                result_bits_count =
+                    std::cmp::max(result_bits_count, (u32::BITS - el.leading_zeros()) as usize);
             }
         }
 
@@ -83,18 +272,18 @@ impl SBox {
     }
 
     pub fn reverse_table(table: &Vec<Vec<u32>>) -> Vec<Vec<u32>> {
-        let result_bits_count = Self::max_bits(&table);
+        let result_bits_count = Self::max_bits(table);
 
         let n = table.len();
         let m = table[0].len();
 
         let mut result = vec![vec![0; m]; n];
-        for i in 0..n {
-            for j in 0..m {
-                let bits = num2bits(table[i][j], result_bits_count);
+        for (i, row) in table.iter().enumerate() {
+            for (j, &el) in row.iter().enumerate() {
+                let bits = num2bits(el, result_bits_count);
                 let (outer_bits, middle_bits) = bits.split_at(Self::ceil_log(n));
 
-                result[bits2num(&outer_bits) as usize][bits2num(middle_bits) as usize] =
+                result[bits2num(outer_bits) as usize][bits2num(middle_bits) as usize] =
                     ((i as u32) << Self::ceil_log(m) as u32) | (j as u32);
             }
         }
@@ -102,63 +291,160 @@ impl SBox {
         result
     }
 
-    fn transform(bits: &[bool], table: &Vec<Vec<u32>>) -> Vec<bool> {
-        let outer_bits_count = Self::ceil_log(table.len());
+    fn reverse_flat_table(table: &[u32], output_bits: usize) -> Vec<u32> {
+        let mut result = vec![0; 1 << output_bits];
+        for (x, &y) in table.iter().enumerate() {
+            result[y as usize] = x as u32;
+        }
+
+        result
+    }
+
+    fn transform(bits: &[bool], table: &[u32], result_bits_count: usize) -> Vec<bool> {
+        num2bits(table[bits2num(bits) as usize], result_bits_count)
+    }
 
-        let (outer_bits, middle_bits) = bits.split_at(outer_bits_count);
+    fn inverse_table(&self) -> &[u32] {
+        self.inverse_table
+            .get_or_init(|| Self::reverse_flat_table(&self.table, self.output_bits))
+    }
 
-        let result_bits_count= Self::max_bits(&table);
+    pub fn input_bits(&self) -> usize {
+        self.input_bits
+    }
 
-        num2bits(
-            table[bits2num(outer_bits) as usize][bits2num(middle_bits) as usize],
-            result_bits_count,
-        )
+    pub fn output_bits(&self) -> usize {
+        self.output_bits
     }
 
     pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
-        Self::transform(bits, &self.table)
+        Self::transform(bits, &self.table, self.output_bits)
     }
 
     pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
-        Self::transform(bits, &self.inverse_table)
+        Self::transform(bits, self.inverse_table(), self.input_bits)
+    }
+
+    pub fn lookup(&self, x: u32) -> u32 {
+        self.table[x as usize]
+    }
+
+    pub fn lookup_inv(&self, y: u32) -> u32 {
+        self.inverse_table()[y as usize]
+    }
+
+    pub fn inverse(&self) -> Result<SBox, &'static str> {
+        if self.input_bits != self.output_bits {
+            return Err("s-box is not bijective");
+        }
+
+        const UNSET: u32 = u32::MAX;
+        let mut inverse_table = vec![UNSET; self.table.len()];
+        for (x, &y) in self.table.iter().enumerate() {
+            if inverse_table[y as usize] != UNSET {
+                return Err("s-box is not bijective");
+            }
+            inverse_table[y as usize] = x as u32;
+        }
+
+        Ok(SBox {
+            table: inverse_table,
+            inverse_table: OnceLock::from(self.table.clone()),
+            input_bits: self.output_bits,
+            output_bits: self.input_bits,
+        })
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn encrypt_batch<B: AsRef<[bool]> + Sync>(&self, blocks: &[B]) -> Vec<Vec<bool>> {
+        blocks
+            .par_iter()
+            .map(|block| self.encrypt(block.as_ref()))
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn decrypt_batch<B: AsRef<[bool]> + Sync>(&self, blocks: &[B]) -> Vec<Vec<bool>> {
+        blocks
+            .par_iter()
+            .map(|block| self.decrypt(block.as_ref()))
+            .collect()
     }
 }
 
+impl PartialEq for SBox {
+    fn eq(&self, other: &SBox) -> bool {
+        self.input_bits == other.input_bits
+            && self.output_bits == other.output_bits
+            && self.table == other.table
+    }
+}
+
+impl Eq for SBox {}
+
+impl Hash for SBox {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.input_bits.hash(state);
+        self.output_bits.hash(state);
+        self.table.hash(state);
+    }
+}
+
+impl Index<u32> for SBox {
+    type Output = u32;
+
+    fn index(&self, x: u32) -> &u32 {
+        &self.table[x as usize]
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct PBox {
     permutation: Vec<u32>,
     inverse_permutation: Vec<u32>,
+    moves: Option<Vec<(usize, usize)>>,
 }
 
 impl PBox {
+    const SPARSE_RATIO: usize = 4;
+
     pub fn new(permutation: Vec<u32>) -> Result<PBox, &'static str> {
         if !Self::is_permutation(&permutation) {
             return Err("invalid permutation");
         }
 
-        Ok(PBox {
+        Ok(Self::from_permutation(permutation))
+    }
+
+    fn from_permutation(permutation: Vec<u32>) -> PBox {
+        let moves: Vec<(usize, usize)> = permutation
+            .iter()
+            .enumerate()
+            .filter(|&(i, &num)| num as usize != i + 1)
+            .map(|(i, &num)| (i, num as usize - 1))
+            .collect();
+
+        PBox {
             inverse_permutation: Self::reverse_permutation(&permutation),
+            moves: (moves.len() * Self::SPARSE_RATIO <= permutation.len()).then_some(moves),
             permutation,
-        })
+        }
     }
 
     fn is_permutation(permutation: &[u32]) -> bool {
         let n = permutation.len();
-        if !(n <= 32) {
-            return false;
-        }
-
-        let mut used = 0u32;
+        let mut used = vec![false; n];
         for &num in permutation {
             if n < num.try_into().unwrap() || num == 0 {
                 return false;
             }
 
-            let bit_num = 1u32 << (num - 1);
-            if (used & bit_num) != 0 {
+            let index = num as usize - 1;
+            if used[index] {
                 return false;
             }
 
-            used |= bit_num;
+            used[index] = true;
         }
 
         true
@@ -184,12 +470,80 @@ impl PBox {
         result
     }
 
+    pub fn len(&self) -> usize {
+        self.permutation.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.permutation.is_empty()
+    }
+
+    pub(crate) fn check_word_size(&self) -> Result<(), &'static str> {
+        if self.len() > 32 {
+            return Err("block size exceeds 32 bits");
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn transform_value(value: u32, permutation: &[u32]) -> u32 {
+        let n = permutation.len();
+        let mut result = 0;
+        for (i, &num) in permutation.iter().enumerate() {
+            result |= ((value >> (n - 1 - i)) & 1) << (n - num as usize);
+        }
+
+        result
+    }
+
+    pub(crate) fn encrypt_value(&self, value: u32) -> u32 {
+        Self::transform_value(value, &self.permutation)
+    }
+
+    pub(crate) fn decrypt_value(&self, value: u32) -> u32 {
+        Self::transform_value(value, &self.inverse_permutation)
+    }
+
     pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
-        Self::transform(bits, &self.permutation[..])
+        match &self.moves {
+            Some(moves) => {
+                let mut result = bits.to_vec();
+                for &(i, j) in moves {
+                    result[j] = bits[i];
+                }
+                result
+            }
+            None => Self::transform(bits, &self.permutation[..]),
+        }
     }
 
     pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
-        Self::transform(bits, &self.inverse_permutation[..])
+        match &self.moves {
+            Some(moves) => {
+                let mut result = bits.to_vec();
+                for &(i, j) in moves {
+                    result[i] = bits[j];
+                }
+                result
+            }
+            None => Self::transform(bits, &self.inverse_permutation[..]),
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn encrypt_batch<B: AsRef<[bool]> + Sync>(&self, blocks: &[B]) -> Vec<Vec<bool>> {
+        blocks
+            .par_iter()
+            .map(|block| self.encrypt(block.as_ref()))
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn decrypt_batch<B: AsRef<[bool]> + Sync>(&self, blocks: &[B]) -> Vec<Vec<bool>> {
+        blocks
+            .par_iter()
+            .map(|block| self.decrypt(block.as_ref()))
+            .collect()
     }
 }
 
@@ -295,6 +649,72 @@ mod tests {
         assert_eq!(a, c_num);
     }
 
+    #[test]
+    fn test_inverse() {
+        let s_box = SBox::from_flat(vec![2, 0, 3, 1], 2, 2).unwrap();
+        let inverse = s_box.inverse().unwrap();
+        assert!(inverse == SBox::from_flat(vec![1, 3, 0, 2], 2, 2).unwrap());
+        assert_eq!(inverse.lookup_inv(3), 1);
+        assert!(inverse.inverse().unwrap() == s_box);
+        assert!(SBox::from_flat(vec![3, 0, 3, 1], 2, 2)
+            .unwrap()
+            .inverse()
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_flat() {
+        let s_box = SBox::from_flat(vec![2, 0, 3, 1], 2, 2).unwrap();
+        assert!(s_box == SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap());
+        assert!(SBox::from_flat(vec![2, 0, 3, 1], 3, 2).is_err());
+        assert!(SBox::from_flat(vec![2, 0, 3, 1], 2, 3).is_err());
+        assert!(SBox::from_flat(vec![2, 0, 4, 1], 2, 2).is_err());
+        assert!(SBox::new(vec![vec![1]]).is_err());
+    }
+
+    #[test]
+    fn test_wide_s_box() {
+        let s_box = SBox::from_fn(16, 16, |x| (x.wrapping_mul(0x9e37) ^ 0x5a5a) & 0xffff).unwrap();
+        assert_eq!(s_box.input_bits(), 16);
+        assert_eq!(s_box.lookup(1), 0x9e37 ^ 0x5a5a);
+        let inverse = s_box.inverse().unwrap();
+        assert_eq!(inverse.lookup(s_box.lookup(0xbeef)), 0xbeef);
+        assert_eq!(s_box.lookup_inv(s_box.lookup(0x1234)), 0x1234);
+
+        let wide = SBox::from_fn(20, 20, |x| x ^ (x >> 3)).unwrap();
+        assert!(wide.inverse().is_ok());
+        assert!(SBox::from_fn(16, 16, |x| x & 0xfffe | 0x8000)
+            .unwrap()
+            .inverse()
+            .is_err());
+        assert!(SBox::from_fn(32, 32, |x| x).is_err());
+    }
+
+    #[test]
+    fn test_lazy_inverse() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        assert!(s_box.inverse_table.get().is_none());
+
+        assert_eq!(s_box.decrypt(&[true, true]), vec![true, false]);
+        assert_eq!(s_box.inverse_table.get(), Some(&vec![1, 3, 0, 2]));
+    }
+
+    #[test]
+    fn test_flat_table() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        assert_eq!(s_box.table, vec![2, 0, 3, 1]);
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0b10, 2))), 3);
+        assert_eq!(bits2num(&s_box.decrypt(&num2bits(3, 2))), 0b10);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        assert_eq!(s_box[0b10], 3);
+        assert_eq!(s_box.lookup(0b10), 3);
+        assert_eq!(s_box.lookup_inv(3), 0b10);
+    }
+
     #[test]
     fn test2() {
         let permutation = vec![4, 2, 7, 1, 3, 8, 5, 6];
@@ -307,4 +727,44 @@ mod tests {
         let c_num = bits2num(&c);
         assert_eq!(a, c_num);
     }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn test_derives() {
+        use std::collections::HashSet;
+
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        s_box.decrypt(&[true, false]);
+        let copy = s_box.clone();
+        assert!(s_box == SBox::new(vec![vec![2, 0, 3, 1]]).unwrap());
+        assert!(copy != SBox::new(vec![vec![0, 2], vec![3, 1]]).unwrap());
+
+        let p_box = PBox::new(vec![2, 1]).unwrap();
+        let set: HashSet<PBox> = [p_box.clone(), p_box, PBox::new(vec![1, 2]).unwrap()].into();
+        assert_eq!(set.len(), 2);
+        let set: HashSet<SBox> = [s_box, copy].into();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_batch() {
+        let permutation = vec![4, 2, 7, 1, 3, 8, 5, 6];
+        let p_box = PBox::new(permutation).unwrap();
+
+        let blocks: Vec<Vec<bool>> = (0..256).map(|a| num2bits(a, 8)).collect();
+        let encrypted = p_box.encrypt_batch(&blocks);
+        for (block, b) in blocks.iter().zip(&encrypted) {
+            assert_eq!(&p_box.encrypt(block), b);
+        }
+        assert_eq!(p_box.decrypt_batch(&encrypted), blocks);
+
+        let s_box = crate::fixtures::heys_s_box();
+        let blocks: Vec<Vec<bool>> = (0..16).map(|a| num2bits(a, 4)).collect();
+        let encrypted = s_box.encrypt_batch(&blocks);
+        for (block, b) in blocks.iter().zip(&encrypted) {
+            assert_eq!(&s_box.encrypt(block), b);
+        }
+        assert_eq!(s_box.decrypt_batch(&encrypted), blocks);
+    }
 }
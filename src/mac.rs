@@ -0,0 +1,126 @@
+use crate::block_cipher::xor;
+use crate::BlockCipher;
+
+fn cbc(cipher: &impl BlockCipher, blocks: &[Vec<bool>]) -> Vec<bool> {
+    blocks
+        .iter()
+        .fold(vec![false; cipher.block_size()], |state, block| {
+            cipher.encrypt_block(&xor(&state, block))
+        })
+}
+
+pub fn cbc_mac(cipher: &impl BlockCipher, message: &[bool]) -> Result<Vec<bool>, &'static str> {
+    let n = cipher.block_size();
+    if message.is_empty() || !message.len().is_multiple_of(n) {
+        return Err("message is not a whole number of blocks");
+    }
+
+    Ok(cbc(
+        cipher,
+        &message.chunks(n).map(<[bool]>::to_vec).collect::<Vec<_>>(),
+    ))
+}
+
+fn reduction_constant(block_size: usize) -> Option<u32> {
+    match block_size {
+        4 => Some(0x3),
+        8 => Some(0x1b),
+        16 => Some(0x2b),
+        32 => Some(0x8d),
+        64 => Some(0x1b),
+        128 => Some(0x87),
+        _ => None,
+    }
+}
+
+fn double(bits: &[bool], constant: u32) -> Vec<bool> {
+    let n = bits.len();
+    let mut result: Vec<bool> = bits[1..].iter().copied().chain([false]).collect();
+    if bits[0] {
+        for i in 0..n.min(32) {
+            result[n - 1 - i] ^= (constant >> i) & 1 == 1;
+        }
+    }
+
+    result
+}
+
+pub fn cmac_subkeys(cipher: &impl BlockCipher) -> Result<(Vec<bool>, Vec<bool>), &'static str> {
+    let n = cipher.block_size();
+    let constant = reduction_constant(n).ok_or("no cmac polynomial for block size")?;
+
+    let l = cipher.encrypt_block(&vec![false; n]);
+    let k1 = double(&l, constant);
+    let k2 = double(&k1, constant);
+
+    Ok((k1, k2))
+}
+
+pub fn cmac(cipher: &impl BlockCipher, message: &[bool]) -> Result<Vec<bool>, &'static str> {
+    let n = cipher.block_size();
+    let (k1, k2) = cmac_subkeys(cipher)?;
+
+    let complete = !message.is_empty() && message.len().is_multiple_of(n);
+    let mut blocks: Vec<Vec<bool>> = message.chunks(n).map(<[bool]>::to_vec).collect();
+    let mut last = blocks.pop().unwrap_or_default();
+    let last = match complete {
+        true => xor(&last, &k1),
+        false => {
+            last.push(true);
+            last.resize(n, false);
+            xor(&last, &k2)
+        }
+    };
+    blocks.push(last);
+
+    Ok(cbc(cipher, &blocks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits, MiniAes};
+
+    #[test]
+    fn test_cbc_mac() {
+        let cipher = MiniAes::new(0xc3f0).unwrap();
+        assert_eq!(
+            bits2num(&cbc_mac(&cipher, &num2bits(0x9c63, 16)).unwrap()),
+            0x72c6
+        );
+
+        let message: Vec<bool> = [0x9c63, 0x1234]
+            .iter()
+            .flat_map(|&block| num2bits(block, 16))
+            .collect();
+        let expected = cipher.encrypt(&num2bits(0x72c6 ^ 0x1234, 16));
+        assert_eq!(cbc_mac(&cipher, &message).unwrap(), expected);
+        assert!(cbc_mac(&cipher, &message[..20]).is_err());
+        assert!(cbc_mac(&cipher, &[]).is_err());
+    }
+
+    #[test]
+    fn test_cmac() {
+        let cipher = MiniAes::new(0xc3f0).unwrap();
+        let l = bits2num(&cipher.encrypt(&num2bits(0, 16)));
+        let double = |x: u32| ((x << 1) & 0xffff) ^ if x & 0x8000 != 0 { 0x2b } else { 0 };
+        let (k1, k2) = cmac_subkeys(&cipher).unwrap();
+        assert_eq!(bits2num(&k1), double(l));
+        assert_eq!(bits2num(&k2), double(double(l)));
+
+        let full = cmac(&cipher, &num2bits(0x9c63, 16)).unwrap();
+        assert_eq!(full, cipher.encrypt(&num2bits(0x9c63 ^ double(l), 16)));
+
+        let partial = cmac(&cipher, &num2bits(0x9c, 8)).unwrap();
+        assert_eq!(
+            partial,
+            cipher.encrypt(&num2bits(0x9c80 ^ double(double(l)), 16))
+        );
+
+        let empty = cmac(&cipher, &[]).unwrap();
+        assert_eq!(
+            empty,
+            cipher.encrypt(&num2bits(0x8000 ^ double(double(l)), 16))
+        );
+    }
+}
@@ -0,0 +1,23 @@
+#[macro_export]
+macro_rules! sbox {
+    ($($value:expr),+ $(,)?) => {
+        const {
+            match $crate::ConstSBox::new([$($value),+]) {
+                Ok(s_box) => s_box,
+                Err(_) => panic!("invalid s-box table"),
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! pbox {
+    ($($num:expr),+ $(,)?) => {
+        const {
+            match $crate::PBoxN::new([$($num),+]) {
+                Ok(p_box) => p_box,
+                Err(_) => panic!("invalid permutation"),
+            }
+        }
+    };
+}
@@ -0,0 +1,103 @@
+use crate::{bits2num, num2bits, SBox};
+
+impl SBox {
+    fn masked_table(&self, input_mask: u32, output_mask: u32) -> Vec<u32> {
+        let mut result = vec![0; self.table.len()];
+        for (x, &y) in self.table.iter().enumerate() {
+            result[x ^ input_mask as usize] = y ^ output_mask;
+        }
+
+        result
+    }
+
+    pub fn encrypt_masked(
+        &self,
+        shares: &[&[bool]],
+        output_masks: &[&[bool]],
+    ) -> Result<Vec<Vec<bool>>, &'static str> {
+        if shares.len() < 2 || output_masks.len() != shares.len() - 1 {
+            return Err("expected at least two shares and one output mask per extra share");
+        }
+        if shares.iter().any(|share| share.len() != self.input_bits)
+            || output_masks
+                .iter()
+                .any(|mask| mask.len() != self.output_bits)
+        {
+            return Err("invalid share length");
+        }
+
+        let input_mask = shares[1..]
+            .iter()
+            .fold(0, |acc, share| acc ^ bits2num(share));
+        let output_mask = output_masks
+            .iter()
+            .fold(0, |acc, mask| acc ^ bits2num(mask));
+        let table = self.masked_table(input_mask, output_mask);
+
+        let mut result = vec![num2bits(
+            table[bits2num(shares[0]) as usize],
+            self.output_bits,
+        )];
+        result.extend(output_masks.iter().map(|mask| mask.to_vec()));
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+
+    #[test]
+    fn test_masked_s_box() {
+        let s_box = heys_s_box();
+
+        for x in 0..16 {
+            let mask = (x * 7 + 3) & 0xf;
+            let output_mask = num2bits((x * 5 + 1) & 0xf, 4);
+            let shares = s_box
+                .encrypt_masked(
+                    &[&num2bits(x ^ mask, 4), &num2bits(mask, 4)],
+                    &[&output_mask],
+                )
+                .unwrap();
+
+            assert_eq!(shares[1], output_mask);
+            assert_eq!(
+                bits2num(&shares[0]) ^ bits2num(&shares[1]),
+                bits2num(&s_box.encrypt(&num2bits(x, 4)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_three_shares() {
+        let s_box = heys_s_box();
+
+        for x in 0..16 {
+            let (a, b) = ((x * 7 + 3) & 0xf, (x * 11 + 5) & 0xf);
+            let output_masks = [num2bits((x * 5 + 1) & 0xf, 4), num2bits(x ^ 0x9, 4)];
+            let shares = s_box
+                .encrypt_masked(
+                    &[&num2bits(x ^ a ^ b, 4), &num2bits(a, 4), &num2bits(b, 4)],
+                    &[&output_masks[0], &output_masks[1]],
+                )
+                .unwrap();
+
+            assert_eq!(shares.len(), 3);
+            assert_eq!(shares[1..], output_masks);
+            assert_eq!(
+                shares.iter().fold(0, |acc, share| acc ^ bits2num(share)),
+                bits2num(&s_box.encrypt(&num2bits(x, 4)))
+            );
+        }
+
+        let share = num2bits(0, 4);
+        assert!(s_box.encrypt_masked(&[&share], &[]).is_err());
+        assert!(s_box.encrypt_masked(&[&share, &share], &[]).is_err());
+        assert!(s_box
+            .encrypt_masked(&[&share, &num2bits(0, 3)], &[&share])
+            .is_err());
+    }
+}
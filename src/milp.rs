@@ -0,0 +1,93 @@
+use crate::Spn;
+
+fn sum(terms: &[String]) -> String {
+    terms.join(" + ")
+}
+
+impl Spn {
+    pub fn to_milp(&self, rounds: usize) -> String {
+        let n = self.block_size();
+        let s_box_size = self.s_box().input_bits();
+
+        let state = |round: usize| -> Vec<String> {
+            (0..n).map(|i| format!("x_{}_{}", round, i)).collect()
+        };
+        let active: Vec<Vec<String>> = (0..rounds)
+            .map(|round| {
+                (0..self.s_box_count())
+                    .map(|j| format!("a_{}_{}", round, j))
+                    .collect()
+            })
+            .collect();
+
+        let mut constraints = Vec::new();
+        constraints.push(format!("{} >= 1", sum(&state(0))));
+        for (round, active) in active.iter().enumerate() {
+            let input = state(round);
+            let next = state(round + 1);
+            let output: Vec<String> = self
+                .p_box()
+                .permutation
+                .iter()
+                .map(|&num| next[num as usize - 1].clone())
+                .collect();
+
+            for (j, a) in active.iter().enumerate() {
+                let x = &input[j * s_box_size..(j + 1) * s_box_size];
+                let y = &output[j * s_box_size..(j + 1) * s_box_size];
+
+                for bit in x {
+                    constraints.push(format!("{} - {} <= 0", bit, a));
+                }
+                constraints.push(format!("{} - {} >= 0", sum(x), a));
+                for (u, v) in [(x, y), (y, x)] {
+                    let scaled: Vec<String> = u
+                        .iter()
+                        .map(|bit| format!("{} {}", s_box_size, bit))
+                        .collect();
+                    constraints.push(format!("{} - {} >= 0", sum(&scaled), v.join(" - ")));
+                }
+            }
+        }
+
+        let mut result = format!(
+            "\\ minimum number of active s-boxes over {} rounds\nMinimize\n obj: {}\nSubject To\n",
+            rounds,
+            sum(&active.concat())
+        );
+        for (i, constraint) in constraints.iter().enumerate() {
+            result.push_str(&format!(" c{}: {}\n", i, constraint));
+        }
+        result.push_str("Binary\n");
+        for round in 0..=rounds {
+            result.push_str(&format!(" {}\n", state(round).join(" ")));
+        }
+        for active in &active {
+            result.push_str(&format!(" {}\n", active.join(" ")));
+        }
+        result.push_str("End\n");
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{num2bits, PBox, SBox, Spn};
+
+    #[test]
+    fn test_milp() {
+        let s_box = SBox::new(vec![vec![0, 3], vec![1, 2]]).unwrap();
+        let p_box = PBox::new(vec![3, 4, 1, 2]).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 4); 2]).unwrap();
+
+        let model = spn.to_milp(1);
+        assert!(model.starts_with("\\ minimum number of active s-boxes over 1 rounds\n"));
+        assert!(model.contains(" obj: a_0_0 + a_0_1\n"));
+        assert!(model.contains(" c0: x_0_0 + x_0_1 + x_0_2 + x_0_3 >= 1\n"));
+        assert!(model.contains(" c3: x_0_0 + x_0_1 - a_0_0 >= 0\n"));
+        assert!(model.contains(" c4: 2 x_0_0 + 2 x_0_1 - x_1_2 - x_1_3 >= 0\n"));
+        assert!(model.contains(" c5: 2 x_1_2 + 2 x_1_3 - x_0_0 - x_0_1 >= 0\n"));
+        assert!(model.ends_with(" a_0_0 a_0_1\nEnd\n"));
+    }
+}
@@ -0,0 +1,152 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{bits2num, num2bits, BlockCipher, SBox};
+
+const MAX_MMAP_BITS: usize = 32;
+
+pub struct MmapSBox {
+    map: Mmap,
+    input_bits: usize,
+    output_bits: usize,
+}
+
+impl MmapSBox {
+    pub fn open(
+        path: impl AsRef<Path>,
+        input_bits: usize,
+        output_bits: usize,
+    ) -> Result<MmapSBox, &'static str> {
+        if input_bits == 0 || input_bits > MAX_MMAP_BITS || output_bits > MAX_MMAP_BITS {
+            return Err("invalid table size");
+        }
+
+        let file = File::open(path).map_err(|_| "cannot open table file")?;
+        // The table file must not be modified while it is mapped.
+        let map = unsafe { Mmap::map(&file) }.map_err(|_| "cannot map table file")?;
+        if map.len() as u64 != 4 << input_bits {
+            return Err("file size does not match input bits");
+        }
+
+        let s_box = MmapSBox {
+            map,
+            input_bits,
+            output_bits,
+        };
+        if output_bits < 32 && s_box.values().any(|y| y >> output_bits != 0) {
+            return Err("table values do not match output bits");
+        }
+
+        Ok(s_box)
+    }
+
+    pub fn input_bits(&self) -> usize {
+        self.input_bits
+    }
+
+    pub fn output_bits(&self) -> usize {
+        self.output_bits
+    }
+
+    pub fn lookup(&self, x: u32) -> u32 {
+        let offset = 4 * x as usize;
+        u32::from_le_bytes(self.map[offset..offset + 4].try_into().unwrap())
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        num2bits(self.lookup(bits2num(bits)), self.output_bits)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = u32> + '_ {
+        self.map
+            .chunks_exact(4)
+            .map(|entry| u32::from_le_bytes(entry.try_into().unwrap()))
+    }
+
+    pub fn is_bijective(&self) -> bool {
+        if self.input_bits != self.output_bits {
+            return false;
+        }
+
+        let mut seen = vec![0u64; (1usize << self.input_bits).div_ceil(64)];
+        self.values().all(|y| {
+            let (word, bit) = (y as usize / 64, y % 64);
+            let fresh = seen[word] >> bit & 1 == 0;
+            seen[word] |= 1 << bit;
+            fresh
+        })
+    }
+
+    pub fn fixed_points(&self) -> usize {
+        self.values()
+            .enumerate()
+            .filter(|&(x, y)| x as u32 == y)
+            .count()
+    }
+
+    pub fn to_s_box(&self) -> Result<SBox, &'static str> {
+        SBox::from_flat(self.values().collect(), self.input_bits, self.output_bits)
+    }
+}
+
+pub fn write_table(
+    path: impl AsRef<Path>,
+    values: impl IntoIterator<Item = u32>,
+) -> Result<(), &'static str> {
+    let file = File::create(path).map_err(|_| "cannot create table file")?;
+    let mut writer = BufWriter::new(file);
+    for y in values {
+        writer
+            .write_all(&y.to_le_bytes())
+            .map_err(|_| "cannot write table file")?;
+    }
+
+    writer.flush().map_err(|_| "cannot write table file")
+}
+
+pub fn write_codebook<C: BlockCipher>(
+    cipher: &C,
+    path: impl AsRef<Path>,
+) -> Result<(), &'static str> {
+    let n = cipher.block_size();
+    if n == 0 || n > MAX_MMAP_BITS {
+        return Err("block size is too large for a codebook");
+    }
+
+    write_table(
+        path,
+        (0..1u64 << n).map(|x| bits2num(&cipher.encrypt_block(&num2bits(x as u32, n)))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codebook, SDes};
+
+    #[test]
+    fn test_mmap() {
+        let path = std::env::temp_dir().join(format!("psboxs-mmap-{}.bin", std::process::id()));
+
+        let s_des = SDes::new(0b1010000010).unwrap();
+        write_codebook(&s_des, &path).unwrap();
+        let table = MmapSBox::open(&path, 8, 8).unwrap();
+        assert_eq!(table.lookup(0b10010111), 0b00111000);
+        assert!(table.is_bijective());
+        assert!(table.to_s_box().unwrap() == codebook(&s_des).unwrap());
+        assert_eq!(
+            table.fixed_points(),
+            codebook(&s_des).unwrap().fixed_points().len()
+        );
+        assert!(MmapSBox::open(&path, 7, 7).is_err());
+        assert!(MmapSBox::open(&path, 8, 4).is_err());
+
+        write_table(&path, [1, 1, 0, 3]).unwrap();
+        assert!(!MmapSBox::open(&path, 2, 2).unwrap().is_bijective());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+use ndarray::{Array2, ArrayView2};
+
+use crate::SBox;
+
+fn to_array<T: Clone>(table: Vec<Vec<T>>) -> Array2<T> {
+    let columns = table.first().map_or(0, Vec::len);
+    Array2::from_shape_vec((table.len(), columns), table.concat()).unwrap()
+}
+
+impl SBox {
+    pub fn table_view(&self) -> ArrayView2<'_, u32> {
+        let columns = 1 << (self.input_bits - self.input_bits / 2);
+        ArrayView2::from_shape((self.table.len() / columns, columns), &self.table).unwrap()
+    }
+
+    pub fn from_array(table: ArrayView2<u32>) -> Result<SBox, &'static str> {
+        SBox::new(table.rows().into_iter().map(|row| row.to_vec()).collect())
+    }
+
+    pub fn ddt_array(&self) -> Array2<u32> {
+        to_array(self.ddt())
+    }
+
+    pub fn lat_array(&self) -> Array2<i32> {
+        to_array(self.lat())
+    }
+
+    pub fn bct_array(&self) -> Array2<u32> {
+        to_array(self.bct())
+    }
+}
+
+impl TryFrom<ArrayView2<'_, u32>> for SBox {
+    type Error = &'static str;
+
+    fn try_from(table: ArrayView2<u32>) -> Result<SBox, &'static str> {
+        SBox::from_array(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+
+    #[test]
+    fn test_ndarray() {
+        let s_box = heys_s_box();
+
+        let view = s_box.table_view();
+        assert_eq!(view.dim(), (4, 4));
+        assert_eq!(view[[1, 2]], 0xb);
+        assert!(SBox::try_from(view).unwrap() == s_box);
+        assert!(SBox::from_array(view.t()).is_ok());
+
+        let ddt = s_box.ddt_array();
+        assert_eq!(ddt.row(0).sum(), 16);
+        assert_eq!(ddt[[0xb, 0x2]], s_box.ddt()[0xb][0x2]);
+        assert_eq!(s_box.lat_array().dim(), (16, 16));
+        assert_eq!(s_box.bct_array()[[0, 0]], 16);
+
+        let wide = Array2::from_elem((2, 2), 4u32);
+        assert!(SBox::from_array(wide.view()).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::SBox;
+
+fn npy_header(descr: &str, shape: &str) -> Vec<u8> {
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        descr, shape
+    );
+    let padding = 63 - (10 + header.len()) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    let mut result = b"\x93NUMPY\x01\x00".to_vec();
+    result.extend((header.len() as u16).to_le_bytes());
+    result.extend(header.bytes());
+
+    result
+}
+
+pub fn npy<T: Copy + Into<i64>>(table: &[Vec<T>]) -> Result<Vec<u8>, &'static str> {
+    let columns = table.first().map_or(0, Vec::len);
+    if table.iter().any(|row| row.len() != columns) {
+        return Err("rows have different lengths");
+    }
+
+    let mut result = npy_header("<i8", &format!("({}, {})", table.len(), columns));
+    for &el in table.iter().flatten() {
+        result.extend(Into::<i64>::into(el).to_le_bytes());
+    }
+
+    Ok(result)
+}
+
+impl SBox {
+    pub fn to_npy(&self) -> Vec<u8> {
+        let mut result = npy_header("<u4", &format!("({},)", self.table.len()));
+        for &y in &self.table {
+            result.extend(y.to_le_bytes());
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_npy() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let bytes = npy(&s_box.lat()).unwrap();
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<i8', 'fortran_order': False, 'shape': (4, 4), }"));
+        assert!(header.ends_with('\n'));
+        assert_eq!(bytes.len(), 10 + header_len + 16 * 8);
+        assert_eq!(
+            i64::from_le_bytes(bytes[10 + header_len..18 + header_len].try_into().unwrap()),
+            2
+        );
+
+        let codebook = s_box.to_npy();
+        assert!(std::str::from_utf8(&codebook[10..74])
+            .unwrap()
+            .contains("'shape': (4,)"));
+        assert_eq!(&codebook[codebook.len() - 4..], &1u32.to_le_bytes());
+
+        assert!(npy(&[vec![1u32], vec![]]).is_err());
+    }
+}
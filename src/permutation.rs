@@ -0,0 +1,329 @@
+use crate::PBox;
+
+impl PBox {
+    pub fn rotate_left(n: usize, k: usize) -> Result<PBox, &'static str> {
+        PBox::new((0..n).map(|i| ((i + n - k % n) % n) as u32 + 1).collect())
+    }
+
+    pub fn rotate_right(n: usize, k: usize) -> Result<PBox, &'static str> {
+        PBox::new((0..n).map(|i| ((i + k) % n) as u32 + 1).collect())
+    }
+
+    pub fn bit_reversal(n: usize) -> Result<PBox, &'static str> {
+        PBox::new((0..n as u32).rev().map(|i| i + 1).collect())
+    }
+
+    pub fn byte_swap(n: usize) -> Result<PBox, &'static str> {
+        if !n.is_multiple_of(8) {
+            return Err("width is not a whole number of bytes");
+        }
+
+        PBox::new(
+            (0..n)
+                .map(|i| ((n / 8 - 1 - i / 8) * 8 + i % 8) as u32 + 1)
+                .collect(),
+        )
+    }
+
+    pub fn transpose(rows: usize, cols: usize) -> Result<PBox, &'static str> {
+        PBox::new(
+            (0..rows * cols)
+                .map(|i| ((i % cols) * rows + i / cols) as u32 + 1)
+                .collect(),
+        )
+    }
+
+    pub fn from_moves(n: usize, moves: &[(u32, u32)]) -> Result<PBox, &'static str> {
+        let mut permutation: Vec<u32> = (1..=n as u32).collect();
+        for &(from, to) in moves {
+            if from == 0 || from as usize > n {
+                return Err("invalid permutation");
+            }
+            permutation[from as usize - 1] = to;
+        }
+
+        PBox::new(permutation)
+    }
+
+    pub fn moved_count(&self) -> usize {
+        self.permutation
+            .iter()
+            .enumerate()
+            .filter(|&(i, &num)| num as usize != i + 1)
+            .count()
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        self.moves.is_some()
+    }
+
+    pub fn encrypt_in_place(&self, bits: &mut [bool]) {
+        match &self.moves {
+            Some(moves) => {
+                let values: Vec<bool> = moves.iter().map(|&(i, _)| bits[i]).collect();
+                for (&(_, j), value) in moves.iter().zip(values) {
+                    bits[j] = value;
+                }
+            }
+            None => {
+                let result = self.encrypt(bits);
+                bits.copy_from_slice(&result);
+            }
+        }
+    }
+
+    pub fn decrypt_in_place(&self, bits: &mut [bool]) {
+        match &self.moves {
+            Some(moves) => {
+                let values: Vec<bool> = moves.iter().map(|&(_, j)| bits[j]).collect();
+                for (&(i, _), value) in moves.iter().zip(values) {
+                    bits[i] = value;
+                }
+            }
+            None => {
+                let result = self.decrypt(bits);
+                bits.copy_from_slice(&result);
+            }
+        }
+    }
+
+    pub fn to_matrix(&self) -> Vec<Vec<bool>> {
+        let n = self.len();
+
+        (0..n)
+            .map(|j| {
+                let i = self.inverse_permutation[j] as usize - 1;
+                (0..n).map(|column| column == i).collect()
+            })
+            .collect()
+    }
+
+    pub fn from_matrix(matrix: &[Vec<bool>]) -> Result<PBox, &'static str> {
+        let n = matrix.len();
+        let mut permutation = vec![0; n];
+        for (j, row) in matrix.iter().enumerate() {
+            let mut ones = row.iter().enumerate().filter(|&(_, &bit)| bit);
+            match (row.len() == n, ones.next(), ones.next()) {
+                (true, Some((i, _)), None) => permutation[i] = j as u32 + 1,
+                _ => return Err("not a permutation matrix"),
+            }
+        }
+
+        PBox::new(permutation).map_err(|_| "not a permutation matrix")
+    }
+
+    pub fn compose(&self, other: &PBox) -> Result<PBox, &'static str> {
+        if self.len() != other.len() {
+            return Err("permutation lengths differ");
+        }
+
+        PBox::new(
+            self.permutation
+                .iter()
+                .map(|&num| other.permutation[num as usize - 1])
+                .collect(),
+        )
+    }
+
+    pub fn pow(&self, k: i64) -> PBox {
+        let base = match k < 0 {
+            true => &self.inverse_permutation,
+            false => &self.permutation,
+        };
+
+        let n = self.len();
+        let mut result: Vec<u32> = (1..=n as u32).collect();
+        let mut square = base.clone();
+        let mut k = k.unsigned_abs();
+        while k != 0 {
+            if k & 1 == 1 {
+                result = result.iter().map(|&num| square[num as usize - 1]).collect();
+            }
+            square = square.iter().map(|&num| square[num as usize - 1]).collect();
+            k >>= 1;
+        }
+
+        Self::from_permutation(result)
+    }
+
+    pub fn order(&self) -> u64 {
+        fn gcd(a: u64, b: u64) -> u64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        self.cycles()
+            .iter()
+            .map(|cycle| cycle.len() as u64)
+            .fold(1, |order, length| order / gcd(order, length) * length)
+    }
+
+    pub fn cycles(&self) -> Vec<Vec<u32>> {
+        let mut visited = vec![false; self.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.len() {
+            let mut cycle = Vec::new();
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                cycle.push(i as u32 + 1);
+                i = self.permutation[i] as usize - 1;
+            }
+            if !cycle.is_empty() {
+                cycles.push(cycle);
+            }
+        }
+
+        cycles
+    }
+
+    pub fn parity(&self) -> usize {
+        (self.len() - self.cycles().len()) % 2
+    }
+
+    pub fn sign(&self) -> i32 {
+        1 - 2 * self.parity() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_permutation;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_compose() {
+        let a = PBox::new(vec![2, 3, 4, 1]).unwrap();
+        let b = PBox::new(vec![4, 3, 2, 1]).unwrap();
+        let bits = [true, true, false, false];
+
+        let composed = a.compose(&b).unwrap();
+        assert_eq!(composed.encrypt(&bits), b.encrypt(&a.encrypt(&bits)));
+        assert_eq!(composed.decrypt(&composed.encrypt(&bits)), bits);
+        assert!(a.compose(&PBox::new(vec![1, 2]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_pow_order() {
+        let p_box = PBox::new(vec![2, 3, 1, 5, 4]).unwrap();
+        let bits = [true, false, false, true, false];
+        assert_eq!(p_box.order(), 6);
+        assert_eq!(p_box.pow(6).permutation, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            p_box.pow(2).encrypt(&bits),
+            p_box.encrypt(&p_box.encrypt(&bits))
+        );
+        assert_eq!(p_box.pow(-1).encrypt(&bits), p_box.decrypt(&bits));
+        assert_eq!(p_box.pow(0).encrypt(&bits), bits);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let p_box = PBox::new(vec![2, 3, 1, 5, 4, 6]).unwrap();
+        assert_eq!(p_box.cycles(), vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+        assert_eq!(p_box.parity(), 1);
+        assert_eq!(p_box.sign(), -1);
+        assert_eq!(p_box.pow(2).sign(), 1);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let bits = num2bits(0b1100_0001, 8);
+        let left = PBox::rotate_left(8, 3).unwrap();
+        let right = PBox::rotate_right(8, 3).unwrap();
+        assert_eq!(bits2num(&left.encrypt(&bits)), 0b0000_1110);
+        assert_eq!(bits2num(&right.encrypt(&bits)), 0b0011_1000);
+        assert_eq!(right.decrypt(&left.decrypt(&bits)), bits);
+        assert_eq!(
+            PBox::rotate_left(8, 11).unwrap().permutation,
+            left.permutation
+        );
+    }
+
+    #[test]
+    fn test_bit_reversal_byte_swap() {
+        let bits = num2bits(0x12_34_56, 24);
+        let reversal = PBox::bit_reversal(24).unwrap();
+        assert_eq!(bits2num(&reversal.encrypt(&bits)), 0x6a_2c_48);
+        assert_eq!(reversal.order(), 2);
+
+        let swap = PBox::byte_swap(24).unwrap();
+        assert_eq!(bits2num(&swap.encrypt(&bits)), 0x56_34_12);
+        assert!(PBox::byte_swap(12).is_err());
+    }
+
+    #[test]
+    fn test_transpose() {
+        let heys = PBox::transpose(4, 4).unwrap();
+        assert_eq!(heys.permutation, heys_permutation());
+
+        let p_box = PBox::transpose(2, 3).unwrap();
+        assert_eq!(p_box.permutation, vec![1, 3, 5, 2, 4, 6]);
+        assert_eq!(
+            p_box
+                .compose(&PBox::transpose(3, 2).unwrap())
+                .unwrap()
+                .permutation,
+            vec![1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_matrix() {
+        let p_box = PBox::new(vec![2, 3, 1]).unwrap();
+        let matrix = p_box.to_matrix();
+        assert_eq!(
+            matrix,
+            vec![
+                vec![false, false, true],
+                vec![true, false, false],
+                vec![false, true, false],
+            ]
+        );
+
+        let bits = [true, false, true];
+        let product: Vec<bool> = matrix
+            .iter()
+            .map(|row| row.iter().zip(&bits).any(|(&m, &x)| m & x))
+            .collect();
+        assert_eq!(product, p_box.encrypt(&bits));
+
+        assert_eq!(
+            PBox::from_matrix(&matrix).unwrap().permutation,
+            vec![2, 3, 1]
+        );
+        assert!(PBox::from_matrix(&[vec![true, true], vec![false, false]]).is_err());
+        assert!(PBox::from_matrix(&[vec![true, false], vec![true, false]]).is_err());
+    }
+
+    #[test]
+    fn test_sparse() {
+        let p_box =
+            PBox::from_moves(64, &[(3, 40), (40, 3), (10, 11), (11, 12), (12, 10)]).unwrap();
+        assert!(p_box.is_sparse());
+        assert_eq!(p_box.moved_count(), 5);
+        assert!(!PBox::transpose(16, 4).unwrap().is_sparse());
+
+        let bits: Vec<bool> = (0..64).map(|i| i % 3 == 0 || i == 9).collect();
+        let expected = PBox::transform(&bits, &p_box.permutation);
+        assert_eq!(p_box.encrypt(&bits), expected);
+        assert_eq!(p_box.decrypt(&expected), bits);
+
+        let mut state = bits.clone();
+        p_box.encrypt_in_place(&mut state);
+        assert_eq!(state, expected);
+        p_box.decrypt_in_place(&mut state);
+        assert_eq!(state, bits);
+
+        let mut state = bits[..16].to_vec();
+        PBox::bit_reversal(16).unwrap().encrypt_in_place(&mut state);
+        assert_eq!(state, bits[..16].iter().rev().copied().collect::<Vec<_>>());
+
+        assert!(PBox::from_moves(4, &[(1, 2)]).is_err());
+        assert!(PBox::from_moves(4, &[(5, 1)]).is_err());
+    }
+}
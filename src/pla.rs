@@ -0,0 +1,133 @@
+use crate::{num2bits, SBox};
+
+fn bit_string(num: u32, bit_count: usize) -> String {
+    num2bits(num, bit_count)
+        .iter()
+        .map(|&bit| if bit { '1' } else { '0' })
+        .collect()
+}
+
+impl SBox {
+    pub fn to_pla(&self) -> String {
+        let mut result = format!(
+            ".i {}\n.o {}\n.type f\n.p {}\n",
+            self.input_bits,
+            self.output_bits,
+            self.table.len()
+        );
+        for (x, &y) in self.table.iter().enumerate() {
+            result.push_str(&format!(
+                "{} {}\n",
+                bit_string(x as u32, self.input_bits),
+                bit_string(y, self.output_bits)
+            ));
+        }
+        result.push_str(".e\n");
+
+        result
+    }
+
+    pub fn from_pla(text: &str) -> Result<SBox, &'static str> {
+        let mut input_bits = None;
+        let mut output_bits = None;
+        let mut cubes = Vec::new();
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let first = fields.next().unwrap();
+            match first {
+                ".i" | ".o" => {
+                    let value = fields
+                        .next()
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .ok_or("invalid pla header")?;
+                    if first == ".i" {
+                        input_bits = Some(value);
+                    } else {
+                        output_bits = Some(value);
+                    }
+                }
+                ".e" | ".end" => break,
+                _ if first.starts_with('.') => {}
+                _ => {
+                    let outputs = fields.next().ok_or("invalid pla cube")?;
+                    cubes.push((first, outputs));
+                }
+            }
+        }
+
+        let input_bits = input_bits.ok_or("missing .i")?;
+        let output_bits = output_bits.ok_or("missing .o")?;
+        if input_bits >= 32 || output_bits >= 32 {
+            return Err("invalid pla header");
+        }
+
+        let mut table = vec![0u32; 1 << input_bits];
+        for (inputs, outputs) in cubes {
+            if inputs.len() != input_bits || outputs.len() != output_bits {
+                return Err("invalid pla cube");
+            }
+
+            let mut care = 0u32;
+            let mut value = 0u32;
+            for ch in inputs.chars() {
+                care <<= 1;
+                value <<= 1;
+                match ch {
+                    '0' => care |= 1,
+                    '1' => {
+                        care |= 1;
+                        value |= 1;
+                    }
+                    '-' | '2' => {}
+                    _ => return Err("invalid pla cube"),
+                }
+            }
+
+            let mut output_mask = 0u32;
+            for ch in outputs.chars() {
+                output_mask = (output_mask << 1) | (ch == '1') as u32;
+            }
+
+            for (x, el) in table.iter_mut().enumerate() {
+                if (x as u32 & care) == value {
+                    *el |= output_mask;
+                }
+            }
+        }
+
+        SBox::new(vec![table])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits2num;
+
+    #[test]
+    fn test_pla() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let pla = s_box.to_pla();
+        assert_eq!(
+            pla,
+            ".i 2\n.o 2\n.type f\n.p 4\n00 10\n01 00\n10 11\n11 01\n.e\n"
+        );
+
+        let minimized = ".i 2\n.o 2\n.p 2\n1- 01\n-0 10\n.e\n";
+        let restored = SBox::from_pla(minimized).unwrap();
+        for a in 0..4 {
+            assert_eq!(
+                bits2num(&restored.encrypt(&num2bits(a, 2))),
+                bits2num(&s_box.encrypt(&num2bits(a, 2)))
+            );
+        }
+
+        assert!(SBox::from_pla(".o 2\n00 10\n").is_err());
+    }
+}
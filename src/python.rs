@@ -0,0 +1,144 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::{bits2num, num2bits, PBox, SBox, Spn};
+
+#[pyclass(name = "SBox")]
+pub struct PySBox {
+    inner: SBox,
+}
+
+#[pymethods]
+impl PySBox {
+    #[new]
+    fn new(table: Vec<u32>) -> PyResult<Self> {
+        Ok(PySBox {
+            inner: SBox::new(vec![table]).map_err(PyValueError::new_err)?,
+        })
+    }
+
+    #[getter]
+    fn input_bits(&self) -> usize {
+        self.inner.input_bits
+    }
+
+    #[getter]
+    fn output_bits(&self) -> usize {
+        self.inner.output_bits
+    }
+
+    fn encrypt(&self, x: u32) -> u32 {
+        bits2num(&self.inner.encrypt(&num2bits(x, self.inner.input_bits)))
+    }
+
+    fn decrypt(&self, y: u32) -> u32 {
+        bits2num(&self.inner.decrypt(&num2bits(y, self.inner.output_bits)))
+    }
+
+    fn table(&self) -> Vec<u32> {
+        self.inner.table.clone()
+    }
+
+    fn ddt(&self) -> Vec<Vec<u32>> {
+        self.inner.ddt()
+    }
+
+    fn lat(&self) -> Vec<Vec<i32>> {
+        self.inner.lat()
+    }
+}
+
+#[pyclass(name = "PBox")]
+pub struct PyPBox {
+    inner: PBox,
+}
+
+#[pymethods]
+impl PyPBox {
+    #[new]
+    fn new(permutation: Vec<u32>) -> PyResult<Self> {
+        let inner = PBox::new(permutation).map_err(PyValueError::new_err)?;
+        inner.check_word_size().map_err(PyValueError::new_err)?;
+
+        Ok(PyPBox { inner })
+    }
+
+    fn encrypt(&self, x: u32) -> u32 {
+        bits2num(&self.inner.encrypt(&num2bits(x, self.inner.len())))
+    }
+
+    fn decrypt(&self, y: u32) -> u32 {
+        bits2num(&self.inner.decrypt(&num2bits(y, self.inner.len())))
+    }
+}
+
+#[pyclass(name = "Spn")]
+pub struct PySpn {
+    inner: Spn,
+}
+
+#[pymethods]
+impl PySpn {
+    #[new]
+    fn new(s_box: &PySBox, p_box: &PyPBox, round_keys: Vec<u32>) -> PyResult<Self> {
+        let block_size = p_box.inner.len();
+        let inner = Spn::new(
+            s_box.inner.clone(),
+            p_box.inner.clone(),
+            round_keys
+                .iter()
+                .map(|&key| num2bits(key, block_size))
+                .collect(),
+        )
+        .map_err(PyValueError::new_err)?;
+
+        Ok(PySpn { inner })
+    }
+
+    fn encrypt(&self, x: u32) -> u32 {
+        bits2num(&self.inner.encrypt(&num2bits(x, self.inner.block_size())))
+    }
+
+    fn decrypt(&self, y: u32) -> u32 {
+        bits2num(&self.inner.decrypt(&num2bits(y, self.inner.block_size())))
+    }
+}
+
+#[pymodule]
+fn psboxs(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PySBox>()?;
+    module.add_class::<PyPBox>()?;
+    module.add_class::<PySpn>()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_permutation, heys_spn, heys_table, HEYS_ROUND_KEYS};
+
+    #[test]
+    fn test_python_wrappers() {
+        let s_box = PySBox::new(heys_table().to_vec()).unwrap();
+        assert_eq!((s_box.input_bits(), s_box.output_bits()), (4, 4));
+        assert_eq!(s_box.encrypt(0x6), 0xb);
+        assert_eq!(s_box.decrypt(0xb), 0x6);
+        assert_eq!(s_box.table()[0x6], 0xb);
+        assert_eq!(s_box.ddt()[0xb][0x2], 8);
+        assert_eq!(s_box.lat().len(), 16);
+        assert!(PySBox::new(vec![0, 0, 1]).is_err());
+
+        let p_box = PyPBox::new(heys_permutation()).unwrap();
+        assert_eq!(p_box.encrypt(0x4000), 0x0800);
+        assert_eq!(p_box.decrypt(0x0800), 0x4000);
+        assert!(PyPBox::new(vec![1, 1]).is_err());
+        assert!(PyPBox::new((1..=64).collect()).is_err());
+
+        let spn = PySpn::new(&s_box, &p_box, HEYS_ROUND_KEYS.to_vec()).unwrap();
+        let expected = heys_spn().encrypt(&num2bits(0x26b7, 16));
+        assert_eq!(spn.encrypt(0x26b7), bits2num(&expected));
+        assert_eq!(spn.decrypt(spn.encrypt(0x26b7)), 0x26b7);
+        assert!(PySpn::new(&s_box, &p_box, vec![0]).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::bits2num;
+
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let mut series = 1.000000000190015;
+    for (i, &c) in COEFFICIENTS.iter().enumerate() {
+        series += c / (x + 1.0 + i as f64);
+    }
+
+    (2.5066282746310005 * series / x).ln() + (x + 0.5) * tmp.ln() - tmp
+}
+
+fn igamc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let prefix = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let (mut term, mut sum, mut n) = (1.0 / a, 1.0 / a, a);
+        while term.abs() > sum.abs() * 1e-15 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+        }
+        return 1.0 - sum * prefix;
+    }
+
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..1000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        d = if d.abs() < tiny { tiny } else { d };
+        c = b + an / c;
+        c = if c.abs() < tiny { tiny } else { c };
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+
+    prefix * h
+}
+
+fn erfc(x: f64) -> f64 {
+    igamc(0.5, x * x)
+}
+
+pub fn monobit_test(bits: &[bool]) -> f64 {
+    let sum: i64 = bits.iter().map(|&bit| if bit { 1 } else { -1 }).sum();
+
+    erfc(sum.abs() as f64 / (2.0 * bits.len() as f64).sqrt())
+}
+
+pub fn runs_test(bits: &[bool]) -> f64 {
+    let n = bits.len() as f64;
+    let pi = bits.iter().filter(|&&bit| bit).count() as f64 / n;
+    if (pi - 0.5).abs() >= 2.0 / n.sqrt() {
+        return 0.0;
+    }
+
+    let runs = 1 + bits.windows(2).filter(|pair| pair[0] != pair[1]).count();
+    let expected = 2.0 * n * pi * (1.0 - pi);
+
+    erfc((runs as f64 - expected).abs() / (2.0 * (2.0 * n).sqrt() * pi * (1.0 - pi)))
+}
+
+fn check_block_length(bits: &[bool], m: usize) -> Result<(), &'static str> {
+    if m == 0 || m >= 32 || m > bits.len() {
+        return Err("invalid block length");
+    }
+
+    Ok(())
+}
+
+pub fn poker_test(bits: &[bool], m: usize) -> Result<f64, &'static str> {
+    check_block_length(bits, m)?;
+
+    let blocks = bits.len() / m;
+    let mut counts = vec![0usize; 1 << m];
+    for block in bits.chunks_exact(m) {
+        counts[bits2num(block) as usize] += 1;
+    }
+
+    let squares: f64 = counts.iter().map(|&c| (c * c) as f64).sum();
+    let chi_square = (1 << m) as f64 / blocks as f64 * squares - blocks as f64;
+
+    Ok(igamc(((1 << m) - 1) as f64 / 2.0, chi_square / 2.0))
+}
+
+fn psi_square(bits: &[bool], m: usize) -> f64 {
+    if m == 0 {
+        return 0.0;
+    }
+
+    let n = bits.len();
+    let mut counts = vec![0usize; 1 << m];
+    for i in 0..n {
+        let mut pattern = 0;
+        for j in 0..m {
+            pattern = (pattern << 1) | bits[(i + j) % n] as usize;
+        }
+        counts[pattern] += 1;
+    }
+
+    let squares: f64 = counts.iter().map(|&c| (c * c) as f64).sum();
+    (1 << m) as f64 / n as f64 * squares - n as f64
+}
+
+pub fn serial_test(bits: &[bool], m: usize) -> Result<(f64, f64), &'static str> {
+    check_block_length(bits, m)?;
+
+    let psi = [
+        psi_square(bits, m),
+        psi_square(bits, m - 1),
+        psi_square(bits, m.saturating_sub(2)),
+    ];
+    let delta = psi[0] - psi[1];
+    let delta_square = psi[0] - 2.0 * psi[1] + psi[2];
+
+    Ok((
+        igamc((1 << (m - 1)) as f64 / 2.0, delta / 2.0),
+        igamc((1 << m.saturating_sub(2)) as f64 / 2.0, delta_square / 2.0),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num2bits;
+
+    fn bits(s: &str) -> Vec<bool> {
+        s.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn test_randomness() {
+        assert!((monobit_test(&bits("1011010101")) - 0.527089).abs() < 1e-6);
+        assert!((runs_test(&bits("1001101011")) - 0.147232).abs() < 1e-6);
+
+        let (p1, p2) = serial_test(&bits("0011011101"), 3).unwrap();
+        assert!((p1 - 0.808792).abs() < 1e-6);
+        assert!((p2 - 0.670320).abs() < 1e-6);
+
+        let all_patterns: Vec<bool> = (0..16).flat_map(|x| num2bits(x, 4)).collect();
+        assert!((poker_test(&all_patterns, 4).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(runs_test(&[true; 100]), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_block_length() {
+        let bits = bits("0011011101");
+        assert!(poker_test(&bits, 0).is_err());
+        assert!(poker_test(&bits, 11).is_err());
+        assert!(serial_test(&bits, 0).is_err());
+        assert!(serial_test(&bits, 11).is_err());
+        assert!(serial_test(&bits, 1).is_ok());
+    }
+}
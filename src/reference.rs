@@ -0,0 +1,157 @@
+use std::fmt;
+
+use crate::SBox;
+
+pub(crate) const PRESENT_S_BOX: [u32; 16] = [
+    0xc, 0x5, 0x6, 0xb, 0x9, 0x0, 0xa, 0xd, 0x3, 0xe, 0xf, 0x8, 0x4, 0x7, 0x1, 0x2,
+];
+
+pub(crate) const HEYS_S_BOX: [u32; 16] = [
+    0xe, 0x4, 0xd, 0x1, 0x2, 0xf, 0xb, 0x8, 0x3, 0xa, 0x6, 0xc, 0x5, 0x9, 0x0, 0x7,
+];
+
+const REFERENCE_4_BIT: [(&str, [u32; 16]); 6] = [
+    ("PRESENT", PRESENT_S_BOX),
+    (
+        "GIFT",
+        [
+            0x1, 0xa, 0x4, 0xc, 0x6, 0xf, 0x3, 0x9, 0x2, 0xd, 0xb, 0x7, 0x5, 0x0, 0x8, 0xe,
+        ],
+    ),
+    (
+        "Serpent S0",
+        [
+            0x3, 0x8, 0xf, 0x1, 0xa, 0x6, 0x5, 0xb, 0xe, 0xd, 0x4, 0x2, 0x7, 0x0, 0x9, 0xc,
+        ],
+    ),
+    (
+        "PRINCE",
+        [
+            0xb, 0xf, 0x3, 0x2, 0xa, 0xc, 0x9, 0x1, 0x6, 0x7, 0x8, 0x0, 0xe, 0x5, 0xd, 0x4,
+        ],
+    ),
+    (
+        "Midori Sb0",
+        [
+            0xc, 0xa, 0xd, 0x3, 0xe, 0xb, 0xf, 0x7, 0x8, 0x9, 0x1, 0x5, 0x0, 0x2, 0x4, 0x6,
+        ],
+    ),
+    ("Heys", HEYS_S_BOX),
+];
+
+fn aes_table() -> Vec<u32> {
+    let multiply = |mut a: u32, mut b: u32| {
+        let mut result = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                result ^= a;
+            }
+            a <<= 1;
+            if a & 0x100 != 0 {
+                a ^= 0x11b;
+            }
+            b >>= 1;
+        }
+        result
+    };
+
+    (0..256u32)
+        .map(|x| {
+            let inverse = (1..256).find(|&y| multiply(x, y) == 1).unwrap_or(0);
+            let mut result = 0x63;
+            for i in 0..5 {
+                result ^= ((inverse << i) | (inverse >> (8 - i))) & 0xff;
+            }
+            result
+        })
+        .collect()
+}
+
+pub fn reference_s_boxes() -> Vec<(&'static str, SBox)> {
+    let mut result: Vec<(&'static str, SBox)> = REFERENCE_4_BIT
+        .iter()
+        .map(|(name, table)| (*name, SBox::from_flat(table.to_vec(), 4, 4).unwrap()))
+        .collect();
+    result.push(("AES", SBox::from_flat(aes_table(), 8, 8).unwrap()));
+
+    result
+}
+
+pub struct RankEntry {
+    pub name: String,
+    pub differential_uniformity: u32,
+    pub linearity: u32,
+}
+
+impl fmt::Display for RankEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: uniformity {}, linearity {}",
+            self.name, self.differential_uniformity, self.linearity
+        )
+    }
+}
+
+impl SBox {
+    pub fn rank_against_references(&self) -> Vec<RankEntry> {
+        let candidate = std::iter::once(("candidate", self.clone()));
+        let mut entries: Vec<RankEntry> = reference_s_boxes()
+            .into_iter()
+            .filter(|(_, s_box)| {
+                s_box.input_bits() == self.input_bits() && s_box.output_bits() == self.output_bits()
+            })
+            .chain(candidate)
+            .map(|(name, s_box)| RankEntry {
+                name: name.to_string(),
+                differential_uniformity: s_box.differential_uniformity(),
+                linearity: s_box.linearity(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| (entry.linearity, entry.differential_uniformity));
+
+        entries
+    }
+
+    pub fn ranking_summary(&self) -> String {
+        self.rank_against_references()
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| format!("{}. {}\n", i + 1, entry))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_s_boxes() {
+        let references = reference_s_boxes();
+        let (_, aes) = references.iter().find(|(name, _)| *name == "AES").unwrap();
+        assert_eq!(aes.lookup(0x00), 0x63);
+        assert_eq!(aes.lookup(0x53), 0xed);
+        assert_eq!(aes.lookup(0xff), 0x16);
+
+        for (name, s_box) in &references[..5] {
+            assert!(s_box.inverse().is_ok(), "{}", name);
+            let uniformity = if *name == "GIFT" { 6 } else { 4 };
+            assert_eq!(s_box.differential_uniformity(), uniformity, "{}", name);
+            assert_eq!(s_box.linearity(), 4, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_ranking() {
+        let identity = SBox::from_flat((0..16).collect(), 4, 4).unwrap();
+        let ranking = identity.rank_against_references();
+        assert_eq!(ranking.len(), 7);
+        assert_eq!(ranking.last().unwrap().name, "candidate");
+        assert_eq!(ranking.last().unwrap().linearity, 8);
+
+        let summary = identity.ranking_summary();
+        assert!(summary.starts_with("1. PRESENT: uniformity 4, linearity 4\n"));
+        assert!(summary.ends_with("7. candidate: uniformity 16, linearity 8\n"));
+    }
+}
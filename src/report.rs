@@ -0,0 +1,318 @@
+use std::fmt;
+
+use crate::SBox;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SBoxReport {
+    pub input_bits: usize,
+    pub output_bits: usize,
+    pub bijective: bool,
+    pub differential_uniformity: u32,
+    pub boomerang_uniformity: Option<u32>,
+    pub linearity: u32,
+    pub nonlinearity: u32,
+    pub degree: usize,
+    pub min_component_degree: usize,
+    pub fixed_points: usize,
+    pub opposite_fixed_points: usize,
+    pub cycle_lengths: Option<Vec<usize>>,
+    pub avalanche_deviation: f64,
+    pub differential_branch_number: u32,
+    pub linear_branch_number: u32,
+}
+
+impl SBox {
+    fn cycle_lengths(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.table.len()];
+        let mut lengths = Vec::new();
+        for start in 0..self.table.len() {
+            let mut length = 0;
+            let mut x = start;
+            while !visited[x] {
+                visited[x] = true;
+                x = self.table[x] as usize;
+                length += 1;
+            }
+            if length != 0 {
+                lengths.push(length);
+            }
+        }
+        lengths.sort_unstable();
+
+        lengths
+    }
+
+    pub fn report(&self) -> SBoxReport {
+        let bijective = self.inverse().is_ok();
+        let degrees: Vec<usize> = self.components().map(|f| f.degree()).collect();
+
+        SBoxReport {
+            input_bits: self.input_bits,
+            output_bits: self.output_bits,
+            bijective,
+            differential_uniformity: self.differential_uniformity(),
+            boomerang_uniformity: bijective.then(|| {
+                self.bct_rows()
+                    .skip(1)
+                    .flat_map(|row| row.into_iter().skip(1))
+                    .max()
+                    .unwrap_or(0)
+            }),
+            linearity: self.linearity(),
+            nonlinearity: self.nonlinearity(),
+            degree: degrees.iter().copied().max().unwrap_or(0),
+            min_component_degree: degrees.iter().copied().min().unwrap_or(0),
+            fixed_points: self.fixed_points().len(),
+            opposite_fixed_points: self.opposite_fixed_points().len(),
+            cycle_lengths: bijective.then(|| self.cycle_lengths()),
+            avalanche_deviation: self
+                .avalanche_matrix()
+                .iter()
+                .flatten()
+                .map(|p| (p - 0.5).abs())
+                .fold(0.0, f64::max),
+            differential_branch_number: self.differential_branch_number(),
+            linear_branch_number: self.linear_branch_number(),
+        }
+    }
+}
+
+pub struct SBoxComparison {
+    pub left: SBoxReport,
+    pub right: SBoxReport,
+    pub differing_entries: Option<usize>,
+    pub bit_distance: Option<u32>,
+    pub permutation_equivalent: Option<bool>,
+}
+
+impl SBoxComparison {
+    fn metrics(report: &SBoxReport) -> [(&'static str, f64); 9] {
+        [
+            (
+                "differential uniformity",
+                report.differential_uniformity as f64,
+            ),
+            (
+                "boomerang uniformity",
+                report.boomerang_uniformity.map_or(f64::NAN, |u| u as f64),
+            ),
+            ("linearity", report.linearity as f64),
+            ("nonlinearity", report.nonlinearity as f64),
+            ("degree", report.degree as f64),
+            ("fixed points", report.fixed_points as f64),
+            ("avalanche deviation", report.avalanche_deviation),
+            (
+                "differential branch number",
+                report.differential_branch_number as f64,
+            ),
+            ("linear branch number", report.linear_branch_number as f64),
+        ]
+    }
+
+    pub fn deltas(&self) -> Vec<(&'static str, f64)> {
+        Self::metrics(&self.left)
+            .iter()
+            .zip(Self::metrics(&self.right))
+            .map(|(&(name, left), (_, right))| (name, right - left))
+            .collect()
+    }
+}
+
+impl fmt::Display for SBoxComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((name, left), (_, right)) in Self::metrics(&self.left)
+            .iter()
+            .zip(Self::metrics(&self.right))
+        {
+            let marker = match left == &right || (left.is_nan() && right.is_nan()) {
+                true => ' ',
+                false => '*',
+            };
+            writeln!(f, "{} {}: {} -> {}", marker, name, left, right)?;
+        }
+        if let (Some(entries), Some(bits)) = (self.differing_entries, self.bit_distance) {
+            writeln!(f, "  differing entries: {} ({} bits)", entries, bits)?;
+        }
+        match self.permutation_equivalent {
+            Some(equivalent) => write!(f, "  permutation equivalent: {}", equivalent),
+            None => write!(f, "  permutation equivalent: not checked"),
+        }
+    }
+}
+
+impl SBox {
+    pub fn compare(&self, other: &SBox) -> SBoxComparison {
+        let same_size =
+            self.input_bits == other.input_bits && self.output_bits == other.output_bits;
+        let differences = || self.table.iter().zip(&other.table).map(|(a, b)| a ^ b);
+
+        SBoxComparison {
+            left: self.report(),
+            right: other.report(),
+            differing_entries: same_size.then(|| differences().filter(|&d| d != 0).count()),
+            bit_distance: same_size.then(|| differences().map(u32::count_ones).sum()),
+            permutation_equivalent: (same_size && self.input_bits <= 6)
+                .then(|| self.is_permutation_equivalent(other)),
+        }
+    }
+}
+
+impl SBoxReport {
+    pub fn to_json(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+        let cycles = self.cycle_lengths.as_ref().map(|lengths| {
+            let lengths: Vec<String> = lengths.iter().map(usize::to_string).collect();
+            format!("[{}]", lengths.join(", "))
+        });
+
+        let fields = [
+            ("input_bits", self.input_bits.to_string()),
+            ("output_bits", self.output_bits.to_string()),
+            ("bijective", self.bijective.to_string()),
+            (
+                "differential_uniformity",
+                self.differential_uniformity.to_string(),
+            ),
+            (
+                "boomerang_uniformity",
+                optional(self.boomerang_uniformity.map(|u| u.to_string())),
+            ),
+            ("linearity", self.linearity.to_string()),
+            ("nonlinearity", self.nonlinearity.to_string()),
+            ("degree", self.degree.to_string()),
+            (
+                "min_component_degree",
+                self.min_component_degree.to_string(),
+            ),
+            ("fixed_points", self.fixed_points.to_string()),
+            (
+                "opposite_fixed_points",
+                self.opposite_fixed_points.to_string(),
+            ),
+            ("cycle_lengths", optional(cycles)),
+            ("avalanche_deviation", self.avalanche_deviation.to_string()),
+            (
+                "differential_branch_number",
+                self.differential_branch_number.to_string(),
+            ),
+            (
+                "linear_branch_number",
+                self.linear_branch_number.to_string(),
+            ),
+        ];
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|(name, value)| format!("  \"{}\": {}", name, value))
+            .collect();
+
+        format!("{{\n{}\n}}", fields.join(",\n"))
+    }
+}
+
+impl fmt::Display for SBoxReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "size: {}x{}", self.input_bits, self.output_bits)?;
+        writeln!(f, "bijective: {}", self.bijective)?;
+        writeln!(
+            f,
+            "differential uniformity: {}",
+            self.differential_uniformity
+        )?;
+        if let Some(uniformity) = self.boomerang_uniformity {
+            writeln!(f, "boomerang uniformity: {}", uniformity)?;
+        }
+        writeln!(f, "linearity: {}", self.linearity)?;
+        writeln!(f, "nonlinearity: {}", self.nonlinearity)?;
+        writeln!(
+            f,
+            "degree: {} (min component {})",
+            self.degree, self.min_component_degree
+        )?;
+        writeln!(
+            f,
+            "fixed points: {} (opposite {})",
+            self.fixed_points, self.opposite_fixed_points
+        )?;
+        if let Some(lengths) = &self.cycle_lengths {
+            writeln!(f, "cycle lengths: {:?}", lengths)?;
+        }
+        writeln!(f, "avalanche deviation: {}", self.avalanche_deviation)?;
+        write!(
+            f,
+            "branch numbers: differential {}, linear {}",
+            self.differential_branch_number, self.linear_branch_number
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::present_s_box;
+
+    #[test]
+    fn test_report() {
+        let report = present_s_box().report();
+        assert!(report.bijective);
+        assert_eq!(report.differential_uniformity, 4);
+        assert_eq!(report.linearity, 4);
+        assert_eq!(report.nonlinearity, 4);
+        assert_eq!(report.degree, 3);
+        assert_eq!(report.min_component_degree, 2);
+        assert_eq!(report.differential_branch_number, 3);
+        assert_eq!(
+            report.cycle_lengths.as_ref().unwrap().iter().sum::<usize>(),
+            16
+        );
+
+        let text = report.to_string();
+        assert!(text.starts_with("size: 4x4\nbijective: true\ndifferential uniformity: 4\n"));
+        let json = report.to_json();
+        assert!(json.starts_with("{\n  \"input_bits\": 4,\n"));
+        assert!(json.contains("\"linearity\": 4,"));
+        assert!(json.ends_with("\"linear_branch_number\": 2\n}"));
+    }
+
+    #[test]
+    fn test_compare() {
+        let identity = SBox::from_flat((0..16).collect(), 4, 4).unwrap();
+        let comparison = present_s_box().compare(&identity);
+        assert_eq!(comparison.differing_entries, Some(16));
+        assert_eq!(
+            comparison.bit_distance,
+            Some(
+                (0..16u32)
+                    .map(|x| (present_s_box().lookup(x) ^ x).count_ones())
+                    .sum()
+            )
+        );
+        assert_eq!(comparison.permutation_equivalent, Some(false));
+        assert!(comparison
+            .deltas()
+            .contains(&("differential uniformity", 12.0)));
+
+        let text = comparison.to_string();
+        assert!(text.starts_with("* differential uniformity: 4 -> 16\n"));
+        assert!(text.ends_with("  permutation equivalent: false"));
+
+        let same = present_s_box().compare(&present_s_box());
+        assert_eq!(same.differing_entries, Some(0));
+        assert_eq!(same.permutation_equivalent, Some(true));
+        assert!(same.deltas().iter().all(|(_, d)| *d == 0.0));
+
+        let smaller = SBox::from_flat(vec![2, 0, 3, 1], 2, 2).unwrap();
+        let comparison = present_s_box().compare(&smaller);
+        assert_eq!(comparison.differing_entries, None);
+        assert!(comparison
+            .to_string()
+            .ends_with("permutation equivalent: not checked"));
+    }
+
+    #[test]
+    fn test_report_not_bijective() {
+        let report = SBox::from_flat(vec![0, 0, 1, 3], 2, 2).unwrap().report();
+        assert!(!report.bijective);
+        assert_eq!(report.boomerang_uniformity, None);
+        assert!(report.to_json().contains("\"cycle_lengths\": null,"));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::SBox;
+
+pub(crate) fn parse_python_int(token: &str) -> Result<u32, &'static str> {
+    let result = if let Some(digits) = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+    {
+        u32::from_str_radix(digits, 16)
+    } else {
+        token.parse()
+    };
+
+    result.map_err(|_| "invalid number in sage s-box")
+}
+
+impl SBox {
+    pub fn to_sage(&self, name: &str) -> String {
+        let values = self
+            .table
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{} = mq.SBox([{}])\n", name, values)
+    }
+
+    pub fn from_sage(text: &str) -> Result<SBox, &'static str> {
+        let start = text.find("SBox(").ok_or("no sage s-box found")? + "SBox(".len();
+        let end = text[start..].rfind(')').ok_or("no sage s-box found")? + start;
+
+        let values = text[start..end]
+            .split(',')
+            .map(|arg| arg.trim_matches(|ch: char| ch == '[' || ch == ']' || ch.is_whitespace()))
+            .filter(|arg| !arg.is_empty() && !arg.contains('='))
+            .map(parse_python_int)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        SBox::new(vec![values])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_sage() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let sage = s_box.to_sage("S");
+        assert_eq!(sage, "S = mq.SBox([2, 0, 3, 1])\n");
+
+        let restored = SBox::from_sage(&sage).unwrap();
+        assert_eq!(bits2num(&restored.encrypt(&num2bits(2, 2))), 3);
+
+        let restored = SBox::from_sage("S = SBox(0x2, 0x0, 0x3, 0x1, big_endian=True)").unwrap();
+        assert_eq!(bits2num(&restored.encrypt(&num2bits(3, 2))), 1);
+
+        assert!(SBox::from_sage("S = [2, 0, 3, 1]").is_err());
+    }
+}
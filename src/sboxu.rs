@@ -0,0 +1,134 @@
+use crate::sage::parse_python_int;
+use crate::SBox;
+
+impl SBox {
+    pub fn to_python_list(&self) -> String {
+        let values = self
+            .table
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("[{}]", values)
+    }
+
+    pub fn from_python_list(text: &str) -> Result<SBox, &'static str> {
+        let start = text.find('[').ok_or("no python list found")?;
+        let end = text[start..].find(']').ok_or("no python list found")? + start;
+
+        let values = text[start + 1..end]
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(parse_python_int)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        SBox::new(vec![values])
+    }
+
+    pub fn to_hex_dump(&self) -> String {
+        let digits = self.output_bits.div_ceil(4).max(1);
+
+        self.table
+            .iter()
+            .map(|&value| format!("{:0digits$x}", value))
+            .collect()
+    }
+
+    pub fn from_hex_dump(text: &str) -> Result<SBox, &'static str> {
+        let text = text.trim();
+        if !text.is_ascii() {
+            return Err("invalid hex dump");
+        }
+
+        let digits = (1..=8)
+            .find(|&digits| {
+                let count = text.len() / digits;
+                text.len().is_multiple_of(digits)
+                    && count > 1
+                    && count.is_power_of_two()
+                    && (count.trailing_zeros() as usize).div_ceil(4) == digits
+            })
+            .ok_or("hex dump length does not match any table size")?;
+
+        let values = (0..text.len())
+            .step_by(digits)
+            .map(|i| u32::from_str_radix(&text[i..i + digits], 16).map_err(|_| "invalid hex dump"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        SBox::new(vec![values])
+    }
+
+    pub fn from_hex(
+        text: &str,
+        input_bits: usize,
+        output_bits: usize,
+    ) -> Result<SBox, &'static str> {
+        let text = text.strip_prefix("0x").unwrap_or(text);
+        let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let width = output_bits.div_ceil(4).max(1);
+        if input_bits >= 32 || digits.len() != width << input_bits {
+            return Err("hex length does not match table size");
+        }
+
+        let values = digits
+            .chunks(width)
+            .map(|chunk| {
+                u32::from_str_radix(&chunk.iter().collect::<String>(), 16)
+                    .map_err(|_| "invalid hex digit")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        SBox::from_flat(values, input_bits, output_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_sboxu_formats() {
+        let s_box =
+            SBox::from_python_list("S = [0xe, 4, 13, 1, 2, 15, 11, 8, 3, 10, 6, 12, 5, 9, 0, 7]\n")
+                .unwrap();
+        assert_eq!(bits2num(&s_box.encrypt(&num2bits(0x6, 4))), 0xb);
+        assert_eq!(
+            s_box.to_python_list(),
+            "[14, 4, 13, 1, 2, 15, 11, 8, 3, 10, 6, 12, 5, 9, 0, 7]"
+        );
+
+        let dump = s_box.to_hex_dump();
+        assert_eq!(dump, "e4d12fb83a6c5907");
+        assert_eq!(SBox::from_hex_dump(&dump).unwrap().to_hex_dump(), dump);
+
+        let s_box = SBox::new(vec![(0..256).map(|x| x ^ 0x5a).collect()]).unwrap();
+        let dump = s_box.to_hex_dump();
+        assert_eq!(&dump[..6], "5a5b58");
+        assert_eq!(SBox::from_hex_dump(&dump).unwrap().to_hex_dump(), dump);
+
+        assert!(SBox::from_hex_dump("e4d12fb83a6c590").is_err());
+        assert!(SBox::from_python_list("(1, 0)").is_err());
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let s_box = SBox::from_hex("e4d12fb8 3a6c5907", 4, 4).unwrap();
+        assert_eq!(s_box.to_hex_dump(), "e4d12fb83a6c5907");
+
+        let aes = crate::reference_s_boxes()
+            .into_iter()
+            .find(|(name, _)| *name == "AES")
+            .unwrap()
+            .1;
+        let blob = aes.to_hex_dump();
+        assert!(blob.starts_with("637c777bf26b6fc5"));
+        assert!(SBox::from_hex(&blob, 8, 8).unwrap() == aes);
+
+        assert!(SBox::from_hex("e4d12fb83a6c5907", 3, 3).is_err());
+        assert!(SBox::from_hex("e4d12fb83a6c590g", 4, 4).is_err());
+    }
+}
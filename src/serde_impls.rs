@@ -0,0 +1,144 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ConstSBox, PBox, PBoxN, SBox, SBoxN, Spn};
+
+#[derive(Serialize, Deserialize)]
+struct SBoxData<T> {
+    input_bits: usize,
+    output_bits: usize,
+    table: T,
+}
+
+impl Serialize for SBox {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SBoxData {
+            input_bits: self.input_bits,
+            output_bits: self.output_bits,
+            table: &self.table,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SBox {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SBoxData::<Vec<u32>>::deserialize(deserializer)?;
+
+        SBox::from_flat(data.table, data.input_bits, data.output_bits).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for PBox {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.permutation.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PBox {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PBox::new(Vec::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpnData<S, P, K> {
+    s_box: S,
+    p_box: P,
+    round_keys: K,
+    t_tables: bool,
+}
+
+impl Serialize for Spn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SpnData {
+            s_box: self.s_box(),
+            p_box: self.p_box(),
+            round_keys: self.round_keys(),
+            t_tables: self.has_t_tables(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Spn {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SpnData::<SBox, PBox, Vec<Vec<bool>>>::deserialize(deserializer)?;
+        let spn = Spn::new(data.s_box, data.p_box, data.round_keys).map_err(D::Error::custom)?;
+
+        match data.t_tables {
+            true => spn.with_t_tables().map_err(D::Error::custom),
+            false => Ok(spn),
+        }
+    }
+}
+
+impl<const IN: usize, const OUT: usize> Serialize for SBoxN<IN, OUT> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_s_box().serialize(serializer)
+    }
+}
+
+impl<'de, const IN: usize, const OUT: usize> Deserialize<'de> for SBoxN<IN, OUT> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SBoxN::from_s_box(SBox::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<const N: usize> Serialize for ConstSBox<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.table()[..].serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ConstSBox<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table: [u32; N] = Vec::deserialize(deserializer)?
+            .try_into()
+            .map_err(|_| D::Error::custom("invalid table size"))?;
+
+        ConstSBox::new(table).map_err(D::Error::custom)
+    }
+}
+
+impl<const N: usize> Serialize for PBoxN<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.permutation()[..].serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for PBoxN<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let permutation: [u32; N] = Vec::deserialize(deserializer)?
+            .try_into()
+            .map_err(|_| D::Error::custom("invalid permutation size"))?;
+
+        PBoxN::new(permutation).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_spn;
+    use crate::{bits2num, num2bits};
+
+    #[test]
+    fn test_serde() {
+        let spn = heys_spn().with_t_tables().unwrap();
+
+        let json = serde_json::to_string(&spn).unwrap();
+        let restored: Spn = serde_json::from_str(&json).unwrap();
+        assert!(restored.has_t_tables());
+
+        let a = num2bits(0x26b7, 16);
+        assert_eq!(restored.encrypt(&a), spn.encrypt(&a));
+        assert_eq!(bits2num(&restored.decrypt(&spn.encrypt(&a))), 0x26b7);
+
+        let json = r#"{"input_bits":2,"output_bits":2,"table":[2,0,3,1]}"#;
+        assert!(serde_json::from_str::<SBox>(json).is_ok());
+        assert!(serde_json::from_str::<PBox>("[1,1,2]").is_err());
+        assert!(serde_json::from_str::<PBoxN<3>>("[3,1,2]").is_ok());
+        assert!(serde_json::from_str::<ConstSBox<4>>("[0,1,1,3]").is_err());
+    }
+}
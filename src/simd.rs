@@ -0,0 +1,58 @@
+use std::array;
+use std::simd::Simd;
+
+const LANES: usize = 32;
+const BLOCKS: usize = LANES / 8;
+
+pub(crate) fn substitute_nibbles(table: &[u32], states: &mut [u64], n: usize) {
+    let mask = u64::MAX >> (64 - n);
+    let lookup = Simd::<u8, LANES>::from_array(array::from_fn(|i| table[i % 16] as u8));
+    let low = Simd::splat(0x0f);
+
+    let mut chunks = states.chunks_exact_mut(BLOCKS);
+    for chunk in &mut chunks {
+        let mut bytes = [0u8; LANES];
+        for (state, out) in chunk.iter().zip(bytes.chunks_exact_mut(8)) {
+            out.copy_from_slice(&state.to_le_bytes());
+        }
+
+        let input = Simd::from_array(bytes);
+        let output = lookup.swizzle_dyn(input & low) | (lookup.swizzle_dyn(input >> 4) << 4);
+        for (state, bytes) in chunk.iter_mut().zip(output.to_array().chunks_exact(8)) {
+            *state = u64::from_le_bytes(bytes.try_into().unwrap()) & mask;
+        }
+    }
+
+    for state in chunks.into_remainder() {
+        *state = (0..16).fold(0, |acc, j| {
+            acc | (table[((*state >> (4 * j)) & 0xf) as usize] as u64) << (4 * j)
+        }) & mask;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_table;
+
+    #[test]
+    fn test_substitute_nibbles() {
+        let table = heys_table();
+        let mut states: Vec<u64> = vec![0x0123_4567_89ab_cdef, 0, u64::MAX, 0x26b7, 0xfedc];
+        substitute_nibbles(&table, &mut states, 64);
+        assert_eq!(
+            states,
+            vec![
+                0xe4d1_2fb8_3a6c_5907,
+                0xeeee_eeee_eeee_eeee,
+                0x7777_7777_7777_7777,
+                0xeeee_eeee_eeee_dbc8,
+                0xeeee_eeee_eeee_7095
+            ]
+        );
+
+        let mut states = vec![0x26b7; 9];
+        substitute_nibbles(&table, &mut states, 16);
+        assert!(states.iter().all(|&state| state == 0xdbc8));
+    }
+}
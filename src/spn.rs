@@ -0,0 +1,481 @@
+use crate::{bits2num, num2bits, PBox, SBox};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[derive(Debug)]
+pub struct Spn {
+    s_box: SBox,
+    p_box: PBox,
+    round_keys: Vec<Vec<bool>>,
+    t_tables: Option<Vec<Vec<u32>>>,
+}
+
+impl Spn {
+    pub fn new(s_box: SBox, p_box: PBox, round_keys: Vec<Vec<bool>>) -> Result<Spn, &'static str> {
+        let block_size = p_box.len();
+        if block_size == 0 || !block_size.is_multiple_of(s_box.input_bits()) {
+            return Err("block size is not a multiple of s-box size");
+        }
+
+        if round_keys.len() < 2 {
+            return Err("not enough round keys");
+        }
+        if round_keys.iter().any(|key| key.len() != block_size) {
+            return Err("invalid round key length");
+        }
+
+        Ok(Spn {
+            s_box,
+            p_box,
+            round_keys,
+            t_tables: None,
+        })
+    }
+
+    pub fn with_t_tables(mut self) -> Result<Spn, &'static str> {
+        self.check_word_size()?;
+
+        let block_size = self.block_size();
+        let s_box_size = self.s_box.input_bits();
+
+        let tables = (0..block_size / s_box_size)
+            .map(|i| {
+                (0..1u32 << s_box_size)
+                    .map(|x| {
+                        let mut state = vec![false; block_size];
+                        state[i * s_box_size..(i + 1) * s_box_size]
+                            .copy_from_slice(&self.s_box.encrypt(&num2bits(x, s_box_size)));
+                        bits2num(&self.p_box.encrypt(&state))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        self.t_tables = Some(tables);
+        Ok(self)
+    }
+
+    pub fn s_box(&self) -> &SBox {
+        &self.s_box
+    }
+
+    pub fn p_box(&self) -> &PBox {
+        &self.p_box
+    }
+
+    pub fn round_keys(&self) -> &[Vec<bool>] {
+        &self.round_keys
+    }
+
+    pub fn has_t_tables(&self) -> bool {
+        self.t_tables.is_some()
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.p_box.len()
+    }
+
+    pub(crate) fn check_word_size(&self) -> Result<(), &'static str> {
+        self.p_box.check_word_size()
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    pub fn reduced(&self, rounds: usize) -> Result<Spn, &'static str> {
+        if rounds == 0 || rounds > self.rounds() {
+            return Err("invalid number of rounds");
+        }
+
+        Ok(Spn {
+            s_box: self.s_box.clone(),
+            p_box: self.p_box.clone(),
+            round_keys: self.round_keys[..=rounds].to_vec(),
+            t_tables: self.t_tables.clone(),
+        })
+    }
+
+    pub fn s_box_count(&self) -> usize {
+        self.block_size() / self.s_box.input_bits()
+    }
+
+    pub(crate) fn chunk(&self, value: u32, index: usize) -> u32 {
+        let s_box_size = self.s_box.input_bits();
+        let shift = self.block_size() - (index + 1) * s_box_size;
+
+        (value >> shift) & ((1 << s_box_size) - 1)
+    }
+
+    pub(crate) fn with_chunk(&self, value: u32, index: usize, chunk: u32) -> u32 {
+        let shift = self.block_size() - (index + 1) * self.s_box.input_bits();
+
+        value | (chunk << shift)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn hex(bits: &[bool]) -> String {
+        let padding = vec![false; bits.len().next_multiple_of(4) - bits.len()];
+        let padded = [&padding[..], bits].concat();
+
+        padded
+            .chunks(4)
+            .map(|digit| char::from_digit(bits2num(digit), 16).unwrap())
+            .collect()
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_layer(layer: &'static str, input: &[bool], output: &[bool]) {
+        tracing::trace!(
+            layer,
+            input = %Self::hex(input),
+            output = %Self::hex(output),
+        );
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    fn trace_layer(_layer: &'static str, _input: &[bool], _output: &[bool]) {}
+
+    fn xor(bits: &[bool], key: &[bool]) -> Vec<bool> {
+        bits.iter().zip(key).map(|(&a, &b)| a ^ b).collect()
+    }
+
+    fn substitute(&self, bits: &[bool]) -> Vec<bool> {
+        bits.chunks(self.s_box.input_bits())
+            .flat_map(|chunk| self.s_box.encrypt(chunk))
+            .collect()
+    }
+
+    fn inverse_substitute(&self, bits: &[bool]) -> Vec<bool> {
+        bits.chunks(self.s_box.output_bits())
+            .flat_map(|chunk| self.s_box.decrypt(chunk))
+            .collect()
+    }
+
+    fn encrypt_t_tables(&self, bits: &[bool], tables: &[Vec<u32>]) -> Vec<bool> {
+        let s_box_size = self.s_box.input_bits();
+        let chunk_mask = (1u32 << s_box_size) - 1;
+
+        let mut state = bits2num(bits);
+        for key in &self.round_keys[..self.rounds()] {
+            state ^= bits2num(key);
+
+            let mut next = 0;
+            for (i, table) in tables.iter().rev().enumerate() {
+                next |= table[((state >> (i * s_box_size)) & chunk_mask) as usize];
+            }
+            state = next;
+        }
+        state ^= bits2num(&self.round_keys[self.rounds()]);
+
+        num2bits(state, self.block_size())
+    }
+
+    pub(crate) fn encrypt_round(&self, bits: &[bool], round: usize) -> Vec<bool> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("round", round).entered();
+
+        let keyed = Self::xor(bits, &self.round_keys[round]);
+        Self::trace_layer("key", bits, &keyed);
+        let substituted = self.substitute(&keyed);
+        Self::trace_layer("substitution", &keyed, &substituted);
+        let permuted = self.p_box.encrypt(&substituted);
+        Self::trace_layer("permutation", &substituted, &permuted);
+
+        permuted
+    }
+
+    fn whiten(&self, bits: &[bool]) -> Vec<bool> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("round", round = self.rounds()).entered();
+
+        let result = Self::xor(bits, &self.round_keys[self.rounds()]);
+        Self::trace_layer("key", bits, &result);
+
+        result
+    }
+
+    pub fn encrypt(&self, bits: &[bool]) -> Vec<bool> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("encrypt").entered();
+
+        if let Some(tables) = &self.t_tables {
+            return self.encrypt_t_tables(bits, tables);
+        }
+
+        let mut state = bits.to_vec();
+        for round in 0..self.rounds() {
+            state = self.encrypt_round(&state, round);
+        }
+
+        self.whiten(&state)
+    }
+
+    pub fn encrypt_masked(
+        &self,
+        shares: &[&[bool]],
+        mut fresh_mask: impl FnMut() -> u32,
+    ) -> Result<Vec<Vec<bool>>, &'static str> {
+        if shares.len() < 2 {
+            return Err("expected at least two shares");
+        }
+        if shares.iter().any(|share| share.len() != self.block_size()) {
+            return Err("invalid share length");
+        }
+
+        let s_box_size = self.s_box.input_bits();
+        let output_bits = self.s_box.output_bits();
+
+        let mut state: Vec<Vec<bool>> = shares.iter().map(|share| share.to_vec()).collect();
+        for key in &self.round_keys[..self.rounds()] {
+            state[0] = Self::xor(&state[0], key);
+
+            let mut next = vec![Vec::new(); state.len()];
+            for i in 0..self.s_box_count() {
+                let chunks: Vec<&[bool]> = state
+                    .iter()
+                    .map(|share| &share[i * s_box_size..(i + 1) * s_box_size])
+                    .collect();
+                let output_masks: Vec<Vec<bool>> = (1..state.len())
+                    .map(|_| num2bits(fresh_mask(), output_bits))
+                    .collect();
+                let output_masks: Vec<&[bool]> = output_masks.iter().map(Vec::as_slice).collect();
+
+                let outputs = self.s_box.encrypt_masked(&chunks, &output_masks)?;
+                for (share, output) in next.iter_mut().zip(outputs) {
+                    share.extend(output);
+                }
+            }
+
+            state = next.iter().map(|share| self.p_box.encrypt(share)).collect();
+        }
+        state[0] = Self::xor(&state[0], &self.round_keys[self.rounds()]);
+
+        Ok(state)
+    }
+
+    pub fn decrypt(&self, bits: &[bool]) -> Vec<bool> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("decrypt").entered();
+
+        let mut state = self.whiten(bits);
+        for round in (0..self.rounds()).rev() {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("round", round).entered();
+
+            let permuted = self.p_box.decrypt(&state);
+            Self::trace_layer("permutation", &state, &permuted);
+            let substituted = self.inverse_substitute(&permuted);
+            Self::trace_layer("substitution", &permuted, &substituted);
+            state = Self::xor(&substituted, &self.round_keys[round]);
+            Self::trace_layer("key", &substituted, &state);
+        }
+
+        state
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn encrypt_batch<B: AsRef<[bool]> + Sync>(&self, blocks: &[B]) -> Vec<Vec<bool>> {
+        blocks
+            .par_iter()
+            .map(|block| self.encrypt(block.as_ref()))
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn decrypt_batch<B: AsRef<[bool]> + Sync>(&self, blocks: &[B]) -> Vec<Vec<bool>> {
+        blocks
+            .par_iter()
+            .map(|block| self.decrypt(block.as_ref()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_spn;
+
+    #[test]
+    fn test_spn() {
+        let spn = heys_spn();
+        for a in [0x0000, 0x26b7, 0xffff] {
+            let a_bits = num2bits(a, 16);
+            let b = spn.encrypt(&a_bits);
+            assert_eq!(bits2num(&spn.decrypt(&b)), a);
+        }
+    }
+
+    #[test]
+    fn test_reduced() {
+        let spn = heys_spn();
+        let reduced = spn.reduced(2).unwrap();
+        assert_eq!(reduced.rounds(), 2);
+
+        let a_bits = num2bits(0x26b7, 16);
+        let state = spn.encrypt_round(&spn.encrypt_round(&a_bits, 0), 1);
+        let expected = Spn::xor(&state, &spn.round_keys()[2]);
+        assert_eq!(reduced.encrypt(&a_bits), expected);
+        assert_eq!(reduced.decrypt(&expected), a_bits);
+        assert_eq!(
+            spn.with_t_tables()
+                .unwrap()
+                .reduced(2)
+                .unwrap()
+                .encrypt(&a_bits),
+            expected
+        );
+
+        assert_eq!(
+            heys_spn().reduced(4).unwrap().round_keys(),
+            heys_spn().round_keys()
+        );
+        assert!(heys_spn().reduced(0).is_err());
+        assert!(heys_spn().reduced(5).is_err());
+    }
+
+    #[test]
+    fn test_masked() {
+        let spn = heys_spn();
+        let mut seed = 0x1234u32;
+        let mut fresh_mask = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 16
+        };
+
+        let a = 0x26b7;
+        let expected = bits2num(&spn.encrypt(&num2bits(a, 16)));
+        let mask = fresh_mask() & 0xffff;
+        let shares = spn
+            .encrypt_masked(
+                &[&num2bits(a ^ mask, 16), &num2bits(mask, 16)],
+                &mut fresh_mask,
+            )
+            .unwrap();
+        assert_eq!(bits2num(&shares[0]) ^ bits2num(&shares[1]), expected);
+
+        let masks = [fresh_mask() & 0xffff, fresh_mask() & 0xffff];
+        let shares = spn
+            .encrypt_masked(
+                &[
+                    &num2bits(a ^ masks[0] ^ masks[1], 16),
+                    &num2bits(masks[0], 16),
+                    &num2bits(masks[1], 16),
+                ],
+                &mut fresh_mask,
+            )
+            .unwrap();
+        assert_eq!(shares.len(), 3);
+        assert_eq!(
+            shares.iter().fold(0, |acc, share| acc ^ bits2num(share)),
+            expected
+        );
+
+        assert!(spn
+            .encrypt_masked(&[&num2bits(a, 16)], &mut fresh_mask)
+            .is_err());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use std::sync::{Arc, Mutex};
+
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        #[derive(Default)]
+        struct Fields {
+            fields: Vec<(String, String)>,
+        }
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.fields
+                    .push((field.name().to_string(), format!("{:?}", value)));
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.fields
+                    .push((field.name().to_string(), value.to_string()));
+            }
+        }
+
+        struct Collector(Arc<Mutex<Vec<Fields>>>);
+
+        impl Subscriber for Collector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                self.0.lock().unwrap().push(fields);
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let present = Spn::present80(0).unwrap();
+        let plaintext = vec![true; 64];
+        tracing::subscriber::with_default(Collector(events.clone()), || {
+            present.encrypt(&plaintext);
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3 * 31 + 1);
+        let first = &events[0].fields;
+        assert_eq!(first[0], ("layer".to_string(), "key".to_string()));
+        assert_eq!(
+            first[1],
+            ("input".to_string(), "ffffffffffffffff".to_string())
+        );
+        assert_eq!(
+            first[2],
+            ("output".to_string(), "ffffffffffffffff".to_string())
+        );
+        assert_eq!(
+            events.last().unwrap().fields[2].1,
+            "a112ffc72f68417b".to_string()
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_batch() {
+        let spn = heys_spn();
+
+        let blocks: Vec<Vec<bool>> = (0..1 << 16).step_by(97).map(|a| num2bits(a, 16)).collect();
+        let encrypted = spn.encrypt_batch(&blocks);
+        for (block, b) in blocks.iter().zip(&encrypted) {
+            assert_eq!(&spn.encrypt(block), b);
+        }
+        assert_eq!(spn.decrypt_batch(&encrypted), blocks);
+    }
+
+    #[test]
+    fn test_t_tables() {
+        let spn = heys_spn();
+        let fast_spn = heys_spn().with_t_tables().unwrap();
+        for a in (0..1 << 16).step_by(97) {
+            let a_bits = num2bits(a, 16);
+            assert_eq!(fast_spn.encrypt(&a_bits), spn.encrypt(&a_bits));
+        }
+
+        assert!(Spn::present80(0).unwrap().with_t_tables().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use std::ops::RangeInclusive;
+
+use arbitrary::{Arbitrary, Unstructured};
+use proptest::prelude::*;
+
+use crate::{num2bits, PBox, SBox, Spn};
+
+pub fn s_box_strategy(bits: RangeInclusive<usize>) -> impl Strategy<Value = SBox> {
+    let bits = (*bits.start()).max(2)..=*bits.end();
+    bits.prop_flat_map(|n| Just((0..1u32 << n).collect::<Vec<u32>>()).prop_shuffle())
+        .prop_map(|table| SBox::new(vec![table]).unwrap())
+}
+
+pub fn p_box_strategy(width: RangeInclusive<usize>) -> impl Strategy<Value = PBox> {
+    width
+        .prop_flat_map(|n| Just((1..=n as u32).collect::<Vec<u32>>()).prop_shuffle())
+        .prop_map(|permutation| PBox::new(permutation).unwrap())
+}
+
+pub fn spn_strategy() -> impl Strategy<Value = Spn> {
+    (2usize..=4, 1usize..=4, 1usize..=4).prop_flat_map(|(s_box_size, count, rounds)| {
+        let n = s_box_size * count;
+        (
+            s_box_strategy(s_box_size..=s_box_size),
+            p_box_strategy(n..=n),
+            prop::collection::vec(0..1u32 << n, rounds + 1),
+        )
+            .prop_map(move |(s_box, p_box, keys)| {
+                let round_keys = keys.into_iter().map(|key| num2bits(key, n)).collect();
+                Spn::new(s_box, p_box, round_keys).unwrap()
+            })
+    })
+}
+
+fn shuffled(u: &mut Unstructured, mut values: Vec<u32>) -> arbitrary::Result<Vec<u32>> {
+    for i in (1..values.len()).rev() {
+        let j = u.int_in_range(0..=i)?;
+        values.swap(i, j);
+    }
+
+    Ok(values)
+}
+
+impl<'a> Arbitrary<'a> for SBox {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<SBox> {
+        let n = u.int_in_range(2..=8)?;
+        let table = shuffled(u, (0..1u32 << n).collect())?;
+
+        Ok(SBox::new(vec![table]).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for PBox {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<PBox> {
+        let n = u.int_in_range(1..=32)?;
+        let permutation = shuffled(u, (1..=n).collect())?;
+
+        Ok(PBox::new(permutation).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Spn {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Spn> {
+        let s_box_size = u.int_in_range(2..=4)?;
+        let n = s_box_size * u.int_in_range(1..=4)?;
+        let s_box = SBox::new(vec![shuffled(u, (0..1u32 << s_box_size).collect())?]).unwrap();
+        let p_box = PBox::new(shuffled(u, (1..=n as u32).collect())?).unwrap();
+
+        let round_keys = (0..u.int_in_range(2..=5)?)
+            .map(|_| Ok(num2bits(u.int_in_range(0..=(1u32 << n) - 1)?, n)))
+            .collect::<arbitrary::Result<_>>()?;
+
+        Ok(Spn::new(s_box, p_box, round_keys).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits2num;
+
+    proptest! {
+        #[test]
+        fn test_s_box_strategy(s_box in s_box_strategy(2..=6), x in 0u32..64) {
+            let x = x % (1 << s_box.input_bits());
+            prop_assert_eq!(s_box.lookup_inv(s_box[x]), x);
+        }
+
+        #[test]
+        fn test_spn_strategy(spn in spn_strategy(), x: u32) {
+            let bits = num2bits(x % (1 << spn.block_size()), spn.block_size());
+            prop_assert_eq!(spn.decrypt(&spn.encrypt(&bits)), bits);
+        }
+    }
+
+    #[test]
+    fn test_arbitrary() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&bytes);
+
+        let s_box = SBox::arbitrary(&mut u).unwrap();
+        let p_box = PBox::arbitrary(&mut u).unwrap();
+        let spn = Spn::arbitrary(&mut u).unwrap();
+        assert_eq!(s_box.lookup(s_box.lookup_inv(0)), 0);
+        assert_eq!(
+            p_box.decrypt(&p_box.encrypt(&vec![true; p_box.len()])),
+            vec![true; p_box.len()]
+        );
+        let bits = num2bits(1, spn.block_size());
+        assert_eq!(bits2num(&spn.decrypt(&spn.encrypt(&bits))), 1);
+    }
+}
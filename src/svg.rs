@@ -0,0 +1,115 @@
+use crate::Spn;
+
+const BIT_SPACING: usize = 20;
+const MARGIN: usize = 20;
+const KEY_HEIGHT: usize = 16;
+const S_BOX_HEIGHT: usize = 24;
+const WIRE_HEIGHT: usize = 20;
+const PERMUTATION_HEIGHT: usize = 40;
+
+fn bit_x(i: usize) -> usize {
+    MARGIN + i * BIT_SPACING + BIT_SPACING / 2
+}
+
+fn line(x1: usize, y1: usize, x2: usize, y2: usize) -> String {
+    format!(
+        "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>\n",
+        x1, y1, x2, y2
+    )
+}
+
+fn labeled_rect(x: usize, y: usize, width: usize, height: usize, label: &str) -> String {
+    format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"black\"/>\n  \
+         <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"12\">{}</text>\n",
+        x,
+        y,
+        width,
+        height,
+        x + width / 2,
+        y + height / 2 + 4,
+        label
+    )
+}
+
+impl Spn {
+    pub fn to_svg(&self) -> String {
+        let n = self.block_size();
+        let s_box_size = self.s_box().input_bits();
+        let width = n * BIT_SPACING + 2 * MARGIN;
+        let round_height = KEY_HEIGHT + S_BOX_HEIGHT + 2 * WIRE_HEIGHT + PERMUTATION_HEIGHT;
+
+        let mut body = String::new();
+        let mut y = MARGIN;
+        for round in 0..=self.rounds() {
+            body.push_str(&labeled_rect(
+                MARGIN,
+                y,
+                n * BIT_SPACING,
+                KEY_HEIGHT,
+                &format!("K{}", round),
+            ));
+            y += KEY_HEIGHT;
+            if round == self.rounds() {
+                break;
+            }
+
+            for i in 0..n {
+                body.push_str(&line(bit_x(i), y, bit_x(i), y + WIRE_HEIGHT));
+            }
+            y += WIRE_HEIGHT;
+
+            for s_box in 0..n / s_box_size {
+                body.push_str(&labeled_rect(
+                    MARGIN + s_box * s_box_size * BIT_SPACING + 2,
+                    y,
+                    s_box_size * BIT_SPACING - 4,
+                    S_BOX_HEIGHT,
+                    "S",
+                ));
+            }
+            y += S_BOX_HEIGHT;
+
+            for (i, &num) in self.p_box().permutation.iter().enumerate() {
+                body.push_str(&line(
+                    bit_x(i),
+                    y,
+                    bit_x(num as usize - 1),
+                    y + PERMUTATION_HEIGHT,
+                ));
+            }
+            y += PERMUTATION_HEIGHT;
+
+            for i in 0..n {
+                body.push_str(&line(bit_x(i), y, bit_x(i), y + WIRE_HEIGHT));
+            }
+            y += WIRE_HEIGHT;
+        }
+
+        let height = MARGIN + self.rounds() * round_height + KEY_HEIGHT + MARGIN;
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n{}</svg>\n",
+            width, height, body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{num2bits, PBox, SBox, Spn};
+
+    #[test]
+    fn test_svg() {
+        let s_box = SBox::new(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        let p_box = PBox::new(vec![1, 3, 2, 4]).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 4); 3]).unwrap();
+
+        let svg = spn.to_svg();
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"120\" height=\"296\">\n"
+        ));
+        assert_eq!(svg.matches("<rect").count(), 3 + 2 * 2);
+        assert_eq!(svg.matches(">K2</text>").count(), 1);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
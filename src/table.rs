@@ -0,0 +1,85 @@
+pub trait AnalysisTable<T> {
+    fn entries(&self) -> impl Iterator<Item = (usize, usize, T)> + '_;
+    fn max_in_row(&self, row: usize) -> Option<(usize, T)>;
+    fn max_in_column(&self, column: usize) -> Option<(usize, T)>;
+    fn nonzero_entries(&self) -> Vec<(usize, usize, T)>;
+    fn rows_with_value(&self, value: T) -> Vec<usize>;
+    fn count_value(&self, value: T) -> usize;
+}
+
+impl<T: Copy + PartialEq + Into<i64>> AnalysisTable<T> for [Vec<T>] {
+    fn entries(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &el)| (i, j, el)))
+    }
+
+    fn max_in_row(&self, row: usize) -> Option<(usize, T)> {
+        self.get(row)?
+            .iter()
+            .copied()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, el)| el.into().abs())
+    }
+
+    fn max_in_column(&self, column: usize) -> Option<(usize, T)> {
+        self.iter()
+            .enumerate()
+            .filter_map(|(i, row)| Some((i, *row.get(column)?)))
+            .rev()
+            .max_by_key(|&(_, el)| el.into().abs())
+    }
+
+    fn nonzero_entries(&self) -> Vec<(usize, usize, T)> {
+        self.entries()
+            .filter(|&(_, _, el)| el.into() != 0)
+            .collect()
+    }
+
+    fn rows_with_value(&self, value: T) -> Vec<usize> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, row)| row.contains(&value))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn count_value(&self, value: T) -> usize {
+        self.entries().filter(|&(_, _, el)| el == value).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_s_box;
+
+    #[test]
+    fn test_ddt_queries() {
+        let ddt = heys_s_box().ddt();
+        assert_eq!(ddt.max_in_row(0xb), Some((0x2, 8)));
+        assert_eq!(ddt.max_in_column(0x2), Some((0xb, 8)));
+        assert_eq!(ddt.rows_with_value(8), vec![0xb]);
+        assert_eq!(ddt.count_value(16), 1);
+        assert_eq!(ddt.entries().count(), 256);
+        assert_eq!(
+            ddt.nonzero_entries()
+                .iter()
+                .map(|&(_, _, el)| el)
+                .sum::<u32>(),
+            256
+        );
+        assert_eq!(ddt.max_in_row(16), None);
+    }
+
+    #[test]
+    fn test_lat_queries() {
+        let lat = heys_s_box().lat();
+        let (_, el) = lat.max_in_row(0x4).unwrap();
+        assert_eq!(el.abs(), 4);
+        assert!(lat.nonzero_entries().contains(&(0x4, 0x5, -4)));
+        assert!(lat.entries().any(|(_, _, el)| el.abs() == 6));
+        assert_eq!(lat.rows_with_value(8), vec![0]);
+    }
+}
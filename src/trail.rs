@@ -0,0 +1,512 @@
+use crate::{bits2num, num2bits, Spn};
+
+const EPSILON: f64 = 1e-9;
+
+pub struct DifferentialTrail {
+    pub differences: Vec<u32>,
+    pub weight: f64,
+}
+
+impl DifferentialTrail {
+    pub fn probability(&self) -> f64 {
+        (-self.weight).exp2()
+    }
+}
+
+pub struct LinearTrail {
+    pub masks: Vec<u32>,
+    pub weight: f64,
+}
+
+impl LinearTrail {
+    pub fn correlation(&self) -> f64 {
+        (-self.weight).exp2()
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.correlation() / 2.0
+    }
+}
+
+struct TrailSearch<'a> {
+    spn: &'a Spn,
+    transitions: Vec<Vec<(u32, f64)>>,
+    first_round: Vec<(u32, u32, f64)>,
+    min_weight: f64,
+    bounds: Vec<f64>,
+    best_weight: f64,
+    best_trail: Vec<u32>,
+    found: Option<Vec<(Vec<u32>, f64)>>,
+}
+
+impl<'a> TrailSearch<'a> {
+    fn new(spn: &'a Spn, transitions: Vec<Vec<(u32, f64)>>) -> TrailSearch<'a> {
+        let mut first_round: Vec<(u32, u32, f64)> = transitions
+            .iter()
+            .enumerate()
+            .skip(1)
+            .flat_map(|(a, row)| row.iter().map(move |&(b, weight)| (a as u32, b, weight)))
+            .collect();
+        first_round.sort_by(|x, y| x.2.total_cmp(&y.2));
+        let min_weight = first_round.first().map_or(0.0, |t| t.2);
+
+        TrailSearch {
+            spn,
+            transitions,
+            first_round,
+            min_weight,
+            bounds: vec![0.0],
+            best_weight: f64::INFINITY,
+            best_trail: Vec::new(),
+            found: None,
+        }
+    }
+
+    fn search(&mut self, rounds: usize) -> f64 {
+        self.best_weight = f64::INFINITY;
+        let mut trail = vec![0; rounds + 1];
+        self.first_round_step(rounds, 0, 0, 0, 0.0, &mut trail);
+
+        self.best_weight
+    }
+
+    fn trails_within(&mut self, rounds: usize, max_weight: f64) -> Vec<(Vec<u32>, f64)> {
+        self.best_weight = max_weight + 2.0 * EPSILON;
+        self.found = Some(Vec::new());
+        let mut trail = vec![0; rounds + 1];
+        self.first_round_step(rounds, 0, 0, 0, 0.0, &mut trail);
+
+        let mut found = self.found.take().unwrap_or_default();
+        found.sort_by(|x, y| x.1.total_cmp(&y.1));
+        found
+    }
+
+    fn first_round_step(
+        &mut self,
+        rounds: usize,
+        index: usize,
+        input: u32,
+        output: u32,
+        weight: f64,
+        trail: &mut [u32],
+    ) {
+        if index == self.spn.s_box_count() {
+            if input != 0 {
+                trail[0] = input;
+                self.round_end(rounds, 0, output, weight, trail);
+            }
+            return;
+        }
+
+        self.first_round_step(rounds, index + 1, input, output, weight, trail);
+        for i in 0..self.first_round.len() {
+            let (a, b, step_weight) = self.first_round[i];
+            if weight + step_weight + self.bounds[rounds - 1] >= self.best_weight - EPSILON {
+                break;
+            }
+
+            self.first_round_step(
+                rounds,
+                index + 1,
+                self.spn.with_chunk(input, index, a),
+                self.spn.with_chunk(output, index, b),
+                weight + step_weight,
+                trail,
+            );
+        }
+    }
+
+    fn round_step(
+        &mut self,
+        rounds: usize,
+        round: usize,
+        index: usize,
+        output: u32,
+        weight: f64,
+        trail: &mut [u32],
+    ) {
+        if index == self.spn.s_box_count() {
+            self.round_end(rounds, round, output, weight, trail);
+            return;
+        }
+
+        let a = self.spn.chunk(trail[round], index) as usize;
+        if a == 0 {
+            self.round_step(rounds, round, index + 1, output, weight, trail);
+            return;
+        }
+
+        let remaining_active = (index + 1..self.spn.s_box_count())
+            .filter(|&j| self.spn.chunk(trail[round], j) != 0)
+            .count();
+        let bound = self.bounds[rounds - round - 1] + remaining_active as f64 * self.min_weight;
+
+        for i in 0..self.transitions[a].len() {
+            let (b, step_weight) = self.transitions[a][i];
+            if weight + step_weight + bound >= self.best_weight - EPSILON {
+                break;
+            }
+
+            self.round_step(
+                rounds,
+                round,
+                index + 1,
+                self.spn.with_chunk(output, index, b),
+                weight + step_weight,
+                trail,
+            );
+        }
+    }
+
+    fn round_end(
+        &mut self,
+        rounds: usize,
+        round: usize,
+        output: u32,
+        weight: f64,
+        trail: &mut [u32],
+    ) {
+        trail[round + 1] = self.spn.p_box().encrypt_value(output);
+        if round + 1 == rounds {
+            if let Some(found) = &mut self.found {
+                found.push((trail.to_vec(), weight));
+            } else if weight < self.best_weight - EPSILON {
+                self.best_weight = weight;
+                self.best_trail = trail.to_vec();
+            }
+            return;
+        }
+
+        self.round_step(rounds, round + 1, 0, 0, weight, trail);
+    }
+}
+
+fn transitions<T: Copy>(
+    rows: impl Iterator<Item = Vec<T>>,
+    weight: impl Fn(T) -> Option<f64>,
+) -> Vec<Vec<(u32, f64)>> {
+    rows.map(|row| {
+        let mut row: Vec<(u32, f64)> = row
+            .into_iter()
+            .enumerate()
+            .filter_map(|(b, el)| weight(el).map(|w| (b as u32, w)))
+            .collect();
+        row.sort_by(|x, y| x.1.total_cmp(&y.1));
+        row
+    })
+    .collect()
+}
+
+impl Spn {
+    fn run_search(
+        &self,
+        transitions: Vec<Vec<(u32, f64)>>,
+        rounds: usize,
+    ) -> Option<TrailSearch<'_>> {
+        if rounds == 0 || rounds > self.rounds() || self.check_word_size().is_err() {
+            return None;
+        }
+
+        let mut search = TrailSearch::new(self, transitions);
+        if search.first_round.is_empty() {
+            return None;
+        }
+        for k in 1..=rounds {
+            let weight = search.search(k);
+            search.bounds.push(weight);
+        }
+
+        Some(search)
+    }
+
+    fn active_s_boxes<T: Copy + Default + PartialEq>(
+        &self,
+        rows: impl Iterator<Item = Vec<T>>,
+        rounds: usize,
+    ) -> Option<Vec<usize>> {
+        let transitions = transitions(rows, |el| (el != T::default()).then_some(1.0));
+
+        let search = self.run_search(transitions, rounds)?;
+        Some(search.bounds[1..].iter().map(|&w| w as usize).collect())
+    }
+
+    pub fn differential_active_s_boxes(&self, rounds: usize) -> Option<Vec<usize>> {
+        self.active_s_boxes(self.s_box().ddt_rows(), rounds)
+    }
+
+    pub fn linear_active_s_boxes(&self, rounds: usize) -> Option<Vec<usize>> {
+        self.active_s_boxes(self.s_box().lat_rows(), rounds)
+    }
+
+    fn differential_transitions(&self) -> Vec<Vec<(u32, f64)>> {
+        let s_box_size = self.s_box().input_bits() as f64;
+        transitions(self.s_box().ddt_rows(), |count| {
+            (count != 0).then(|| s_box_size - (count as f64).log2())
+        })
+    }
+
+    pub fn best_differential_trail(&self, rounds: usize) -> Option<DifferentialTrail> {
+        let search = self.run_search(self.differential_transitions(), rounds)?;
+        Some(DifferentialTrail {
+            differences: search.best_trail,
+            weight: search.best_weight,
+        })
+    }
+
+    pub(crate) fn differential_trails_within(
+        &self,
+        rounds: usize,
+        slack: f64,
+    ) -> Option<Vec<DifferentialTrail>> {
+        let mut search = self.run_search(self.differential_transitions(), rounds)?;
+        let max_weight = search.bounds[rounds] + slack;
+
+        let trails = search
+            .trails_within(rounds, max_weight)
+            .into_iter()
+            .map(|(differences, weight)| DifferentialTrail {
+                differences,
+                weight,
+            })
+            .collect();
+        Some(trails)
+    }
+
+    fn check_differences(&self, differences: &[u32]) -> Result<(), &'static str> {
+        self.check_word_size()?;
+        if differences.len() < 2 || differences.len() > self.rounds() + 1 {
+            return Err("invalid trail length");
+        }
+        if differences
+            .iter()
+            .any(|&d| (d as u64) >> self.block_size() != 0)
+        {
+            return Err("difference does not fit the block");
+        }
+
+        Ok(())
+    }
+
+    pub fn evaluate_differential_trail(
+        &self,
+        differences: Vec<u32>,
+    ) -> Result<DifferentialTrail, &'static str> {
+        self.check_differences(&differences)?;
+
+        let s_box_size = self.s_box().input_bits() as f64;
+        let mut weight = 0.0;
+        for pair in differences.windows(2) {
+            let output = self.p_box().decrypt_value(pair[1]);
+            for j in 0..self.s_box_count() {
+                let count =
+                    self.s_box().ddt_row(self.chunk(pair[0], j))[self.chunk(output, j) as usize];
+                weight += s_box_size - (count as f64).log2();
+            }
+        }
+
+        Ok(DifferentialTrail {
+            differences,
+            weight,
+        })
+    }
+
+    pub fn sample_differential_trail(
+        &self,
+        trail: &DifferentialTrail,
+        samples: usize,
+        mut random: impl FnMut() -> u32,
+    ) -> Result<f64, &'static str> {
+        self.check_differences(&trail.differences)?;
+        if samples == 0 {
+            return Err("no samples");
+        }
+
+        let n = self.block_size();
+        let mask = (u64::MAX >> (64 - n)) as u32;
+
+        let mut hits = 0;
+        for _ in 0..samples {
+            let a = random() & mask;
+            let mut x = num2bits(a, n);
+            let mut y = num2bits(a ^ trail.differences[0], n);
+
+            let mut follows = true;
+            for (round, &difference) in trail.differences[1..].iter().enumerate() {
+                x = self.encrypt_round(&x, round);
+                y = self.encrypt_round(&y, round);
+                if bits2num(&x) ^ bits2num(&y) != difference {
+                    follows = false;
+                    break;
+                }
+            }
+            hits += follows as usize;
+        }
+
+        Ok(hits as f64 / samples as f64)
+    }
+
+    pub fn best_linear_trail(&self, rounds: usize) -> Option<LinearTrail> {
+        let s_box_size = self.s_box().input_bits() as f64;
+        let transitions = transitions(self.s_box().lat_rows(), |bias: i32| {
+            (bias != 0).then(|| s_box_size - 1.0 - (bias.unsigned_abs() as f64).log2())
+        });
+
+        let search = self.run_search(transitions, rounds)?;
+        Some(LinearTrail {
+            masks: search.best_trail,
+            weight: search.best_weight,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_p_box, heys_s_box};
+    use crate::{num2bits, PBox};
+
+    fn best_weight_by_dynamic_programming(
+        spn: &Spn,
+        rounds: usize,
+        step_weight: impl Fn(u32, u32) -> f64,
+    ) -> f64 {
+        let n = spn.block_size();
+
+        let mut best = vec![0.0f64; 1 << n];
+        best[0] = f64::INFINITY;
+        for _ in 0..rounds {
+            let mut next = vec![f64::INFINITY; 1 << n];
+            for x in 1..1u32 << n {
+                for z in 1..1u32 << n {
+                    let mut weight = best[x as usize];
+                    for j in 0..spn.s_box_count() {
+                        weight += step_weight(spn.chunk(x, j), spn.chunk(z, j));
+                    }
+
+                    let y = spn.p_box().encrypt_value(z) as usize;
+                    next[y] = next[y].min(weight);
+                }
+            }
+            best = next;
+        }
+
+        best.into_iter().fold(f64::INFINITY, f64::min)
+    }
+
+    fn toy_spn() -> Spn {
+        let p_box = PBox::new(vec![1, 5, 2, 6, 3, 7, 4, 8]).unwrap();
+        Spn::new(heys_s_box(), p_box, vec![num2bits(0, 8); 4]).unwrap()
+    }
+
+    #[test]
+    fn test_best_differential_trail() {
+        let spn = toy_spn();
+        let ddt = spn.s_box().ddt();
+
+        for rounds in 1..=3 {
+            let trail = spn.best_differential_trail(rounds).unwrap();
+            let expected = best_weight_by_dynamic_programming(&spn, rounds, |a, b| {
+                4.0 - (ddt[a as usize][b as usize] as f64).log2()
+            });
+            assert_eq!(trail.differences.len(), rounds + 1);
+            assert!((trail.weight - expected).abs() < 1e-9);
+        }
+        assert!(spn.best_differential_trail(4).is_none());
+    }
+
+    #[test]
+    fn test_best_linear_trail() {
+        let spn = toy_spn();
+        let lat = spn.s_box().lat();
+
+        for rounds in 1..=3 {
+            let trail = spn.best_linear_trail(rounds).unwrap();
+            let expected = best_weight_by_dynamic_programming(&spn, rounds, |a, b| {
+                3.0 - (lat[a as usize][b as usize].abs() as f64).log2()
+            });
+            assert_eq!(trail.masks.len(), rounds + 1);
+            assert!((trail.weight - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_active_s_boxes() {
+        let spn = toy_spn();
+        let ddt = spn.s_box().ddt();
+
+        let active = spn.differential_active_s_boxes(3).unwrap();
+        assert_eq!(active.len(), 3);
+        for (rounds, &count) in (1..=3).zip(&active) {
+            let expected = best_weight_by_dynamic_programming(&spn, rounds, |a, b| {
+                match (a, ddt[a as usize][b as usize]) {
+                    (_, 0) => f64::INFINITY,
+                    (0, _) => 0.0,
+                    _ => 1.0,
+                }
+            });
+            assert_eq!(count as f64, expected);
+        }
+        assert_eq!(spn.linear_active_s_boxes(1), Some(vec![1]));
+        assert!(spn.linear_active_s_boxes(4).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_differential_trail() {
+        let spn = toy_spn();
+        let best = spn.best_differential_trail(2).unwrap();
+
+        let trail = spn
+            .evaluate_differential_trail(best.differences.clone())
+            .unwrap();
+        assert!((trail.weight - best.weight).abs() < 1e-9);
+
+        let mut seed = 0x1234u32;
+        let random = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 16
+        };
+        let probability = spn.sample_differential_trail(&trail, 4096, random).unwrap();
+        assert!((probability - trail.probability()).abs() < 0.05);
+
+        assert!(spn.evaluate_differential_trail(vec![1]).is_err());
+        assert!(spn.evaluate_differential_trail(vec![0x100, 1]).is_err());
+        assert!(spn.sample_differential_trail(&trail, 0, || 0).is_err());
+        for differences in [vec![], vec![1; spn.rounds() + 2], vec![0x100, 1]] {
+            let invalid = DifferentialTrail {
+                differences,
+                weight: 0.0,
+            };
+            assert!(spn.sample_differential_trail(&invalid, 1, || 0).is_err());
+        }
+        let impossible = spn.evaluate_differential_trail(vec![0x10, 0x80]).unwrap();
+        assert_eq!(impossible.probability(), 0.0);
+    }
+
+    #[test]
+    fn test_heys_trail() {
+        let p_box = heys_p_box();
+        let spn = Spn::new(heys_s_box(), p_box, vec![num2bits(0, 16); 4]).unwrap();
+
+        let trail = spn.best_differential_trail(3).unwrap();
+        assert!(trail.probability() >= 27.0 / 1024.0);
+
+        let trail = spn.best_linear_trail(3).unwrap();
+        assert!(trail.bias() >= 1.0 / 32.0);
+    }
+
+    #[test]
+    fn test_present_is_rejected() {
+        let spn = Spn::present80(0).unwrap();
+        let trail = DifferentialTrail {
+            differences: vec![1, 1],
+            weight: 0.0,
+        };
+
+        assert!(spn.differential_active_s_boxes(2).is_none());
+        assert!(spn.linear_active_s_boxes(2).is_none());
+        assert!(spn.best_differential_trail(1).is_none());
+        assert!(spn.best_linear_trail(1).is_none());
+        assert!(spn.evaluate_differential_trail(vec![1, 1]).is_err());
+        assert!(spn.sample_differential_trail(&trail, 1, || 0).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use crate::Spn;
+
+impl Spn {
+    fn pattern_bit(&self, index: usize) -> u32 {
+        1 << (self.s_box_count() - 1 - index)
+    }
+
+    fn pattern(&self, difference: u32) -> u32 {
+        (0..self.s_box_count())
+            .filter(|&j| self.chunk(difference, j) != 0)
+            .fold(0, |acc, j| acc | self.pattern_bit(j))
+    }
+
+    pub fn truncated_pattern(&self, difference: u32) -> Result<u32, &'static str> {
+        self.check_word_size()?;
+
+        Ok(self.pattern(difference))
+    }
+
+    pub fn truncated_transitions(&self, pattern: u32) -> Result<Vec<(u32, f64)>, &'static str> {
+        self.check_word_size()?;
+
+        let s_box_size = self.s_box().input_bits();
+        let ddt = self.s_box().ddt();
+        let total = ((1u64 << s_box_size) * ((1 << s_box_size) - 1)) as f64;
+
+        let mut result = BTreeMap::from([(0u32, 1.0f64)]);
+        for j in (0..self.s_box_count()).filter(|&j| pattern & self.pattern_bit(j) != 0) {
+            let mut touched = BTreeMap::new();
+            for b in 0..1u32 << s_box_size {
+                let count: u32 = ddt[1..].iter().map(|row| row[b as usize]).sum();
+                let next = self.pattern(self.p_box().encrypt_value(self.with_chunk(0, j, b)));
+                *touched.entry(next).or_insert(0.0) += count as f64 / total;
+            }
+
+            let mut next = BTreeMap::new();
+            for (&a, &p) in &result {
+                for (&b, &q) in &touched {
+                    *next.entry(a | b).or_insert(0.0) += p * q;
+                }
+            }
+            result = next;
+        }
+
+        Ok(result.into_iter().filter(|&(_, p)| p > 0.0).collect())
+    }
+
+    pub fn truncated_probability(&self, patterns: &[u32]) -> Result<f64, &'static str> {
+        patterns
+            .windows(2)
+            .map(|pair| {
+                Ok(self
+                    .truncated_transitions(pair[0])?
+                    .into_iter()
+                    .find(|&(pattern, _)| pattern == pair[1])
+                    .map_or(0.0, |(_, p)| p))
+            })
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{heys_s_box, heys_table};
+    use crate::{bits2num, num2bits, PBox, SBox, Spn};
+
+    fn check_transitions(spn: &Spn) {
+        assert_eq!(spn.truncated_transitions(0), Ok(vec![(0, 1.0)]));
+        for pattern in 1..4 {
+            let mut counts = [0usize; 4];
+            let mut total = 0;
+            for difference in (1..1u32 << 8).filter(|&d| spn.truncated_pattern(d) == Ok(pattern)) {
+                for x in 0..1u32 << 8 {
+                    let a = spn.encrypt_round(&num2bits(x, 8), 0);
+                    let b = spn.encrypt_round(&num2bits(x ^ difference, 8), 0);
+                    let next = spn.truncated_pattern(bits2num(&a) ^ bits2num(&b)).unwrap();
+                    counts[next as usize] += 1;
+                    total += 1;
+                }
+            }
+
+            let transitions = spn.truncated_transitions(pattern).unwrap();
+            for (next, &count) in counts.iter().enumerate() {
+                let p = transitions
+                    .iter()
+                    .find(|&&(pattern, _)| pattern == next as u32)
+                    .map_or(0.0, |&(_, p)| p);
+                assert!((p - count as f64 / total as f64).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncated_transitions() {
+        let p_box = PBox::new(vec![1, 5, 2, 6, 3, 7, 4, 8]).unwrap();
+        let spn = Spn::new(heys_s_box(), p_box.clone(), vec![num2bits(0, 8); 2]).unwrap();
+        check_transitions(&spn);
+        assert_eq!(spn.truncated_probability(&[0b10, 0b00]), Ok(0.0));
+
+        let collapsed: Vec<u32> = heys_table().iter().map(|&y| y & 0xe).collect();
+        let s_box = SBox::from_flat(collapsed, 4, 4).unwrap();
+        let spn = Spn::new(s_box, p_box, vec![num2bits(0, 8); 2]).unwrap();
+        check_transitions(&spn);
+        assert!(spn.truncated_probability(&[0b10, 0b00]).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_present_is_rejected() {
+        let spn = Spn::present80(0).unwrap();
+        assert!(spn.truncated_pattern(1).is_err());
+        assert!(spn.truncated_transitions(1).is_err());
+        assert!(spn.truncated_probability(&[1, 1]).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+
+use crate::block_cipher::xor;
+use crate::{bits2num, num2bits, PBox, SBox, Spn};
+
+const EXHAUSTIVE_BITS: usize = 16;
+const SAMPLES: usize = 4096;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Finding {
+    NotBijective {
+        collisions: usize,
+    },
+    InverseMismatch {
+        output: u32,
+        inverse: u32,
+    },
+    RoundTrip {
+        input: Vec<bool>,
+        output: Vec<bool>,
+    },
+    WidthMismatch {
+        layer: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    TTableMismatch {
+        input: Vec<bool>,
+    },
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hex = |bits: &[bool]| {
+            format!(
+                "{:0width$x}",
+                bits2num(bits),
+                width = bits.len().div_ceil(4)
+            )
+        };
+        match self {
+            Finding::NotBijective { collisions } => {
+                write!(
+                    f,
+                    "not bijective: {} outputs are hit more than once",
+                    collisions
+                )
+            }
+            Finding::InverseMismatch { output, inverse } => write!(
+                f,
+                "inverse table maps {:x} to {:x}, which does not map back",
+                output, inverse
+            ),
+            Finding::RoundTrip { input, output } => {
+                write!(f, "decrypt(encrypt({})) = {}", hex(input), hex(output))
+            }
+            Finding::WidthMismatch {
+                layer,
+                expected,
+                found,
+            } => write!(f, "{} has width {}, expected {}", layer, found, expected),
+            Finding::TTableMismatch { input } => {
+                write!(f, "t-tables disagree with the layers on {}", hex(input))
+            }
+        }
+    }
+}
+
+fn test_inputs(width: usize) -> Vec<Vec<bool>> {
+    if width <= EXHAUSTIVE_BITS {
+        return (0..1u32 << width).map(|x| num2bits(x, width)).collect();
+    }
+
+    let mut seed = 0x2545f491u32;
+    (0..SAMPLES)
+        .map(|_| {
+            (0..width)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    (seed >> 16) & 1 == 1
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl SBox {
+    pub fn verify(&self) -> Vec<Finding> {
+        let mut hits = vec![0usize; 1 << self.output_bits];
+        for &y in &self.table {
+            hits[y as usize] += 1;
+        }
+        let collisions = hits.iter().filter(|&&count| count > 1).count();
+        if collisions != 0 {
+            return vec![Finding::NotBijective { collisions }];
+        }
+
+        let mut findings: Vec<Finding> = self
+            .inverse_table()
+            .iter()
+            .enumerate()
+            .filter(|&(y, &x)| self.table[x as usize] != y as u32)
+            .map(|(y, &x)| Finding::InverseMismatch {
+                output: y as u32,
+                inverse: x,
+            })
+            .collect();
+
+        findings.extend(
+            (0..self.table.len() as u32)
+                .map(|x| num2bits(x, self.input_bits))
+                .filter_map(|input| {
+                    let output = self.decrypt(&self.encrypt(&input));
+                    (output != input).then_some(Finding::RoundTrip { input, output })
+                }),
+        );
+
+        findings
+    }
+}
+
+impl PBox {
+    pub fn verify(&self) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = self
+            .inverse_permutation
+            .iter()
+            .enumerate()
+            .filter(|&(i, &j)| self.permutation[j as usize - 1] != i as u32 + 1)
+            .map(|(i, &j)| Finding::InverseMismatch {
+                output: i as u32 + 1,
+                inverse: j,
+            })
+            .collect();
+
+        findings.extend(test_inputs(self.len()).into_iter().filter_map(|input| {
+            let output = self.decrypt(&self.encrypt(&input));
+            (output != input).then_some(Finding::RoundTrip { input, output })
+        }));
+
+        findings
+    }
+}
+
+impl Spn {
+    pub fn verify(&self) -> Vec<Finding> {
+        let n = self.block_size();
+        let s_box_size = self.s_box().input_bits();
+
+        let mut findings = Vec::new();
+        if self.s_box().output_bits() != s_box_size {
+            findings.push(Finding::WidthMismatch {
+                layer: "s-box output",
+                expected: s_box_size,
+                found: self.s_box().output_bits(),
+            });
+        }
+        if !n.is_multiple_of(s_box_size) {
+            findings.push(Finding::WidthMismatch {
+                layer: "substitution layer",
+                expected: n,
+                found: n / s_box_size * s_box_size,
+            });
+        }
+        for key in self.round_keys() {
+            if key.len() != n {
+                findings.push(Finding::WidthMismatch {
+                    layer: "round key",
+                    expected: n,
+                    found: key.len(),
+                });
+            }
+        }
+        if !findings.is_empty() {
+            return findings;
+        }
+
+        findings.extend(self.s_box().verify());
+        findings.extend(self.p_box().verify());
+        if !findings.is_empty() {
+            return findings;
+        }
+
+        for input in test_inputs(n) {
+            let ciphertext = self.encrypt(&input);
+            if self.has_t_tables() {
+                let mut state = input.clone();
+                for round in 0..self.rounds() {
+                    state = self.encrypt_round(&state, round);
+                }
+                if xor(&state, &self.round_keys()[self.rounds()]) != ciphertext {
+                    findings.push(Finding::TTableMismatch {
+                        input: input.clone(),
+                    });
+                }
+            }
+
+            let output = self.decrypt(&ciphertext);
+            if output != input {
+                findings.push(Finding::RoundTrip { input, output });
+            }
+        }
+
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_s_box, heys_spn};
+
+    #[test]
+    fn test_verify() {
+        assert!(heys_s_box().verify().is_empty());
+
+        let collapsed = SBox::from_flat(vec![3, 0, 3, 1], 2, 2).unwrap();
+        assert_eq!(
+            collapsed.verify(),
+            vec![Finding::NotBijective { collisions: 1 }]
+        );
+
+        let p_box = PBox::transpose(16, 4).unwrap();
+        assert!(p_box.verify().is_empty());
+        let wide = PBox::new((1..=40).rev().collect()).unwrap();
+        assert!(wide.verify().is_empty());
+
+        let spn = heys_spn();
+        assert!(spn.verify().is_empty());
+        assert!(spn.with_t_tables().unwrap().verify().is_empty());
+        assert!(Spn::present80(0).unwrap().verify().is_empty());
+    }
+
+    #[test]
+    fn test_verify_findings() {
+        let collapsed = SBox::from_flat(vec![3, 0, 3, 1], 2, 2).unwrap();
+        let p_box = PBox::new(vec![3, 4, 1, 2]).unwrap();
+        let spn = Spn::new(collapsed, p_box, vec![num2bits(0, 4); 3]).unwrap();
+
+        let findings = spn.verify();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].to_string(),
+            "not bijective: 1 outputs are hit more than once"
+        );
+    }
+}
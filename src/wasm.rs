@@ -0,0 +1,130 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{bits2num, num2bits, PBox, SBox, Spn};
+
+#[wasm_bindgen(js_name = SBox)]
+pub struct WasmSBox {
+    inner: SBox,
+}
+
+#[wasm_bindgen(js_class = SBox)]
+impl WasmSBox {
+    #[wasm_bindgen(constructor)]
+    pub fn new(table: Vec<u32>) -> Result<WasmSBox, JsError> {
+        Ok(WasmSBox {
+            inner: SBox::new(vec![table]).map_err(JsError::new)?,
+        })
+    }
+
+    #[wasm_bindgen(getter, js_name = inputBits)]
+    pub fn input_bits(&self) -> usize {
+        self.inner.input_bits
+    }
+
+    #[wasm_bindgen(getter, js_name = outputBits)]
+    pub fn output_bits(&self) -> usize {
+        self.inner.output_bits
+    }
+
+    pub fn encrypt(&self, x: u32) -> u32 {
+        bits2num(&self.inner.encrypt(&num2bits(x, self.inner.input_bits)))
+    }
+
+    pub fn decrypt(&self, y: u32) -> u32 {
+        bits2num(&self.inner.decrypt(&num2bits(y, self.inner.output_bits)))
+    }
+
+    pub fn ddt(&self) -> Vec<u32> {
+        self.inner.ddt().concat()
+    }
+
+    pub fn lat(&self) -> Vec<i32> {
+        self.inner.lat().concat()
+    }
+}
+
+#[wasm_bindgen(js_name = PBox)]
+pub struct WasmPBox {
+    inner: PBox,
+}
+
+#[wasm_bindgen(js_class = PBox)]
+impl WasmPBox {
+    #[wasm_bindgen(constructor)]
+    pub fn new(permutation: Vec<u32>) -> Result<WasmPBox, JsError> {
+        let inner = PBox::new(permutation).map_err(JsError::new)?;
+        inner.check_word_size().map_err(JsError::new)?;
+
+        Ok(WasmPBox { inner })
+    }
+
+    pub fn encrypt(&self, x: u32) -> u32 {
+        bits2num(&self.inner.encrypt(&num2bits(x, self.inner.len())))
+    }
+
+    pub fn decrypt(&self, y: u32) -> u32 {
+        bits2num(&self.inner.decrypt(&num2bits(y, self.inner.len())))
+    }
+}
+
+#[wasm_bindgen(js_name = Spn)]
+pub struct WasmSpn {
+    inner: Spn,
+}
+
+#[wasm_bindgen(js_class = Spn)]
+impl WasmSpn {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        s_box: &WasmSBox,
+        p_box: &WasmPBox,
+        round_keys: Vec<u32>,
+    ) -> Result<WasmSpn, JsError> {
+        let block_size = p_box.inner.len();
+        let inner = Spn::new(
+            s_box.inner.clone(),
+            p_box.inner.clone(),
+            round_keys
+                .iter()
+                .map(|&key| num2bits(key, block_size))
+                .collect(),
+        )
+        .map_err(JsError::new)?;
+
+        Ok(WasmSpn { inner })
+    }
+
+    pub fn encrypt(&self, x: u32) -> u32 {
+        bits2num(&self.inner.encrypt(&num2bits(x, self.inner.block_size())))
+    }
+
+    pub fn decrypt(&self, y: u32) -> u32 {
+        bits2num(&self.inner.decrypt(&num2bits(y, self.inner.block_size())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{heys_permutation, heys_spn, heys_table, HEYS_ROUND_KEYS};
+
+    #[test]
+    fn test_wasm_wrappers() {
+        let s_box = WasmSBox::new(heys_table().to_vec()).unwrap();
+        assert_eq!((s_box.input_bits(), s_box.output_bits()), (4, 4));
+        assert_eq!(s_box.encrypt(0x6), 0xb);
+        assert_eq!(s_box.decrypt(0xb), 0x6);
+        assert_eq!(s_box.ddt()[0xb * 16 + 0x2], 8);
+        assert_eq!(s_box.lat().len(), 256);
+
+        let p_box = WasmPBox::new(heys_permutation()).unwrap();
+        assert_eq!(p_box.encrypt(0x8000), 0x8000);
+        assert_eq!(p_box.encrypt(0x4000), 0x0800);
+        assert_eq!(p_box.decrypt(0x0800), 0x4000);
+
+        let spn = WasmSpn::new(&s_box, &p_box, HEYS_ROUND_KEYS.to_vec()).unwrap();
+        let expected = heys_spn().encrypt(&num2bits(0x26b7, 16));
+        assert_eq!(spn.encrypt(0x26b7), bits2num(&expected));
+        assert_eq!(spn.decrypt(spn.encrypt(0x26b7)), 0x26b7);
+    }
+}
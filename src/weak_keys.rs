@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::SBox;
+
+pub struct WeakKeyCriteria {
+    pub min_nonlinearity: u32,
+    pub max_fixed_points: usize,
+    pub require_bijective: bool,
+}
+
+impl Default for WeakKeyCriteria {
+    fn default() -> Self {
+        WeakKeyCriteria {
+            min_nonlinearity: 1,
+            max_fixed_points: 2,
+            require_bijective: true,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum WeakKeyIssue {
+    LowNonlinearity(u32),
+    FixedPoints(usize),
+    NotBijective,
+}
+
+impl fmt::Display for WeakKeyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeakKeyIssue::LowNonlinearity(nonlinearity) => {
+                write!(f, "nonlinearity is only {}", nonlinearity)
+            }
+            WeakKeyIssue::FixedPoints(count) => write!(f, "{} fixed points", count),
+            WeakKeyIssue::NotBijective => write!(f, "s-box is not bijective"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyDiagnostics {
+    pub round: usize,
+    pub nonlinearity: u32,
+    pub fixed_points: usize,
+    pub bijective: bool,
+    pub issues: Vec<WeakKeyIssue>,
+}
+
+impl KeyDiagnostics {
+    pub fn is_weak(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for KeyDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.issues.is_empty() {
+            return write!(f, "round {}: ok", self.round);
+        }
+
+        let issues: Vec<String> = self.issues.iter().map(|issue| issue.to_string()).collect();
+        write!(f, "round {}: {}", self.round, issues.join(", "))
+    }
+}
+
+impl SBox {
+    fn key_diagnostics(&self, round: usize, criteria: &WeakKeyCriteria) -> KeyDiagnostics {
+        let nonlinearity = self.nonlinearity();
+        let fixed_points = self.fixed_points().len();
+        let bijective = self.inverse().is_ok();
+
+        let mut issues = Vec::new();
+        if nonlinearity < criteria.min_nonlinearity {
+            issues.push(WeakKeyIssue::LowNonlinearity(nonlinearity));
+        }
+        if fixed_points > criteria.max_fixed_points {
+            issues.push(WeakKeyIssue::FixedPoints(fixed_points));
+        }
+        if criteria.require_bijective && !bijective {
+            issues.push(WeakKeyIssue::NotBijective);
+        }
+
+        KeyDiagnostics {
+            round,
+            nonlinearity,
+            fixed_points,
+            bijective,
+            issues,
+        }
+    }
+}
+
+pub fn screen_round_keys(
+    generator: impl Fn(&[bool]) -> SBox,
+    round_keys: &[Vec<bool>],
+    criteria: &WeakKeyCriteria,
+) -> Vec<KeyDiagnostics> {
+    round_keys
+        .iter()
+        .enumerate()
+        .map(|(round, key)| generator(key).key_diagnostics(round, criteria))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::heys_table;
+    use crate::{bits2num, num2bits};
+
+    fn generator(key: &[bool]) -> SBox {
+        let heys = heys_table();
+        let k = bits2num(key);
+        let table = (0..16u32)
+            .map(|x| match k {
+                0 => x,
+                1 => heys[x as usize] & 0xe,
+                _ => heys[(x ^ k) as usize] ^ k,
+            })
+            .collect();
+
+        SBox::from_flat(table, 4, 4).unwrap()
+    }
+
+    #[test]
+    fn test_screen_round_keys() {
+        let round_keys: Vec<Vec<bool>> = [0x0, 0x1, 0x6].iter().map(|&k| num2bits(k, 4)).collect();
+        let diagnostics = screen_round_keys(generator, &round_keys, &WeakKeyCriteria::default());
+
+        assert_eq!(
+            diagnostics[0].issues,
+            vec![
+                WeakKeyIssue::LowNonlinearity(0),
+                WeakKeyIssue::FixedPoints(16)
+            ]
+        );
+        assert!(diagnostics[1].issues.contains(&WeakKeyIssue::NotBijective));
+        assert!(!diagnostics[2].is_weak());
+        assert_eq!(diagnostics[2].to_string(), "round 2: ok");
+    }
+}